@@ -0,0 +1,88 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, GenericParam, Ident, Index, parse_macro_input, parse_quote};
+
+// Derives `StableHash` by hashing struct fields in declaration order, and for
+// enums a `u32` variant index followed by the variant's fields.
+#[proc_macro_derive(StableHash)]
+pub fn derive_stable_hash(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds
+                .push(parse_quote!(::hash::core::domain::stable_hash::StableHash));
+        }
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, writes) = destructure(&data.fields);
+            quote! {
+                let Self #pattern = self;
+                #writes
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().enumerate().map(|(index, variant)| {
+                let name = &variant.ident;
+                let index = index as u32;
+                let (pattern, writes) = destructure(&variant.fields);
+                quote! {
+                    Self::#name #pattern => {
+                        state.write(&#index.to_le_bytes());
+                        #writes
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return syn::Error::new(Span::call_site(), "StableHash cannot be derived for unions")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::hash::core::domain::stable_hash::StableHash for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn stable_hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+fn destructure(fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let bindings: Vec<Ident> = (0..fields.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote! { { #(#names: #bindings),* } }
+        }
+        Fields::Unnamed(_) => {
+            let indices = (0..fields.len()).map(Index::from);
+            quote! { { #(#indices: #bindings),* } }
+        }
+        Fields::Unit => quote! {},
+    };
+
+    let writes = quote! {
+        #(::hash::core::domain::stable_hash::StableHash::stable_hash(#bindings, state);)*
+    };
+
+    (pattern, writes)
+}
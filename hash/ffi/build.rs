@@ -0,0 +1,39 @@
+use std::{env, fs, path::PathBuf};
+
+// The header is generated into `OUT_DIR` on every build; the committed copy in
+// `include/` is only touched on request, so builds never modify the source
+// tree. `HASH_FFI_HEADER=write` refreshes it (`make ffi-header`) and
+// `HASH_FFI_HEADER=check` fails the build if it is stale (`make ffi-header-check`).
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+
+    let bindings = cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("failed to generate C header");
+    bindings.write_to_file(out_dir.join("hash_engine.h"));
+
+    let committed = crate_dir.join("include/hash_engine.h");
+    match env::var("HASH_FFI_HEADER").as_deref() {
+        Ok("write") => {
+            bindings.write_to_file(&committed);
+        }
+        Ok("check") => {
+            let mut generated = Vec::new();
+            bindings.write(&mut generated);
+            if fs::read(&committed).ok().as_deref() != Some(generated.as_slice()) {
+                panic!("include/hash_engine.h is out of date; run `make ffi-header`");
+            }
+        }
+        Ok(other) => panic!("HASH_FFI_HEADER must be `write` or `check`, not `{}`", other),
+        Err(_) => {}
+    }
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=include/hash_engine.h");
+    println!("cargo:rerun-if-env-changed=HASH_FFI_HEADER");
+}
@@ -0,0 +1,112 @@
+#ifndef HASH_ENGINE_H
+#define HASH_ENGINE_H
+
+/* Generated by cbindgen from ffi/src/lib.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Bumped whenever a signature or struct layout in this file changes.
+ */
+#define HASH_ENGINE_ABI_VERSION 1
+
+/**
+ * Opaque streaming state. Create with `hash_engine_new`, release with
+ * `hash_engine_free`.
+ */
+typedef struct HashEngineState HashEngineState;
+
+/**
+ * 128-bit digest: `hi` equals the 64-bit hash of the same input, `lo` holds
+ * the next 64 bits of extended output.
+ */
+typedef struct HashEngineDigest128 {
+  uint64_t hi;
+  uint64_t lo;
+} HashEngineDigest128;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+uint32_t hash_engine_abi_version(void);
+
+/**
+ * One-shot hash with the default seed.
+ *
+ * # Safety
+ *
+ * `data` must point to `len` readable bytes; it may be NULL when `len` is 0.
+ */
+uint64_t hash_engine_hash(const uint8_t *data, size_t len);
+
+/**
+ * One-shot hash with an explicit seed.
+ *
+ * # Safety
+ *
+ * `data` must point to `len` readable bytes; it may be NULL when `len` is 0.
+ */
+uint64_t hash_engine_hash_seeded(const uint8_t *data, size_t len, uint64_t seed);
+
+/**
+ * One-shot 128-bit hash with an explicit seed.
+ *
+ * # Safety
+ *
+ * `data` must point to `len` readable bytes; it may be NULL when `len` is 0.
+ */
+struct HashEngineDigest128 hash_engine_hash128(const uint8_t *data, size_t len, uint64_t seed);
+
+/**
+ * Allocates a streaming state. Never returns NULL.
+ */
+struct HashEngineState *hash_engine_new(uint64_t seed);
+
+/**
+ * Feeds `len` bytes into the state.
+ *
+ * # Safety
+ *
+ * `state` must come from `hash_engine_new` and not have been freed. `data`
+ * must point to `len` readable bytes; it may be NULL when `len` is 0.
+ */
+void hash_engine_update(struct HashEngineState *state, const uint8_t *data, size_t len);
+
+/**
+ * Returns the hash of everything written so far. The state stays usable.
+ *
+ * # Safety
+ *
+ * `state` must come from `hash_engine_new` and not have been freed.
+ */
+uint64_t hash_engine_finish(const struct HashEngineState *state);
+
+/**
+ * Returns the 128-bit hash of everything written so far.
+ *
+ * # Safety
+ *
+ * `state` must come from `hash_engine_new` and not have been freed.
+ */
+struct HashEngineDigest128 hash_engine_finish128(const struct HashEngineState *state);
+
+/**
+ * Releases a state. Passing NULL is a no-op.
+ *
+ * # Safety
+ *
+ * `state` must be NULL or come from `hash_engine_new`, and must not be used
+ * again afterwards.
+ */
+void hash_engine_free(struct HashEngineState *state);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* HASH_ENGINE_H */
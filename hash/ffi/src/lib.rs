@@ -0,0 +1,136 @@
+//! C ABI for `HashEngine`. Every function here produces the same values as the
+//! Rust API; `include/hash_engine.h` is regenerated from this file on build.
+
+use core::{hash::Hasher, slice};
+
+use hash::{constants::DEFAULT_SEED, core::domain::hash_engine::HashEngine, hash_oneshot};
+
+/// Bumped whenever a signature or struct layout in this file changes.
+pub const HASH_ENGINE_ABI_VERSION: u32 = 1;
+
+/// Opaque streaming state. Create with `hash_engine_new`, release with
+/// `hash_engine_free`.
+pub struct HashEngineState(HashEngine);
+
+/// 128-bit digest: `hi` equals the 64-bit hash of the same input, `lo` holds
+/// the next 64 bits of extended output.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct HashEngineDigest128 {
+    pub hi: u64,
+    pub lo: u64,
+}
+
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(data, len) }
+    }
+}
+
+fn digest128(engine: &HashEngine) -> HashEngineDigest128 {
+    let value = engine.finish_digest128().as_u128();
+    HashEngineDigest128 {
+        hi: (value >> 64) as u64,
+        lo: value as u64,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn hash_engine_abi_version() -> u32 {
+    HASH_ENGINE_ABI_VERSION
+}
+
+/// One-shot hash with the default seed.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes; it may be NULL when `len` is 0.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hash_engine_hash(data: *const u8, len: usize) -> u64 {
+    hash_oneshot(unsafe { bytes(data, len) }, DEFAULT_SEED)
+}
+
+/// One-shot hash with an explicit seed.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes; it may be NULL when `len` is 0.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hash_engine_hash_seeded(data: *const u8, len: usize, seed: u64) -> u64 {
+    hash_oneshot(unsafe { bytes(data, len) }, seed)
+}
+
+/// One-shot 128-bit hash with an explicit seed.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes; it may be NULL when `len` is 0.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hash_engine_hash128(
+    data: *const u8,
+    len: usize,
+    seed: u64,
+) -> HashEngineDigest128 {
+    let mut engine = HashEngine::new(seed);
+    engine.write(unsafe { bytes(data, len) });
+    digest128(&engine)
+}
+
+/// Allocates a streaming state. Never returns NULL.
+#[unsafe(no_mangle)]
+pub extern "C" fn hash_engine_new(seed: u64) -> *mut HashEngineState {
+    Box::into_raw(Box::new(HashEngineState(HashEngine::new(seed))))
+}
+
+/// Feeds `len` bytes into the state.
+///
+/// # Safety
+///
+/// `state` must come from `hash_engine_new` and not have been freed. `data`
+/// must point to `len` readable bytes; it may be NULL when `len` is 0.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hash_engine_update(
+    state: *mut HashEngineState,
+    data: *const u8,
+    len: usize,
+) {
+    let state = unsafe { &mut *state };
+    state.0.write(unsafe { bytes(data, len) });
+}
+
+/// Returns the hash of everything written so far. The state stays usable.
+///
+/// # Safety
+///
+/// `state` must come from `hash_engine_new` and not have been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hash_engine_finish(state: *const HashEngineState) -> u64 {
+    unsafe { &*state }.0.finish()
+}
+
+/// Returns the 128-bit hash of everything written so far.
+///
+/// # Safety
+///
+/// `state` must come from `hash_engine_new` and not have been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hash_engine_finish128(
+    state: *const HashEngineState,
+) -> HashEngineDigest128 {
+    digest128(&unsafe { &*state }.0)
+}
+
+/// Releases a state. Passing NULL is a no-op.
+///
+/// # Safety
+///
+/// `state` must be NULL or come from `hash_engine_new`, and must not be used
+/// again afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn hash_engine_free(state: *mut HashEngineState) {
+    if !state.is_null() {
+        drop(unsafe { Box::from_raw(state) });
+    }
+}
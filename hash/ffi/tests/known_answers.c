@@ -0,0 +1,92 @@
+/* Checks the C ABI against vectors/known_answers.txt, the same vectors the
+ * Rust quality suite verifies. Usage: known_answers <path to vectors file> */
+
+#include <inttypes.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "hash_engine.h"
+
+#define DEFAULT_SEED UINT64_C(0xcafecafecafecafe)
+#define MAX_LEN 4096
+#define STREAM_CHUNK 7
+
+static int failures = 0;
+
+static void expect(const char *what, size_t len, uint64_t seed, uint64_t actual, uint64_t expected) {
+    if (actual != expected) {
+        fprintf(stderr, "FAIL %s len=%zu seed=%016" PRIx64 ": got %016" PRIx64 ", want %016" PRIx64 "\n",
+                what, len, seed, actual, expected);
+        failures++;
+    }
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <known_answers.txt>\n", argv[0]);
+        return 2;
+    }
+
+    FILE *file = fopen(argv[1], "r");
+    if (!file) {
+        perror(argv[1]);
+        return 2;
+    }
+
+    if (hash_engine_abi_version() != HASH_ENGINE_ABI_VERSION) {
+        fprintf(stderr, "FAIL abi version mismatch\n");
+        failures++;
+    }
+
+    static uint8_t input[MAX_LEN];
+    for (size_t i = 0; i < MAX_LEN; i++) {
+        input[i] = (uint8_t)(i % 251);
+    }
+
+    char line[256];
+    int vectors = 0;
+
+    while (fgets(line, sizeof line, file)) {
+        if (line[0] == '#' || line[0] == '\n') {
+            continue;
+        }
+
+        size_t len;
+        uint64_t seed, h64, hi, lo;
+        if (sscanf(line, "%zu %" SCNx64 " %" SCNx64 " %" SCNx64 " %" SCNx64, &len, &seed, &h64, &hi, &lo) != 5 ||
+            len > MAX_LEN) {
+            fprintf(stderr, "malformed vector: %s", line);
+            return 2;
+        }
+        vectors++;
+
+        const uint8_t *data = len ? input : NULL;
+
+        if (seed == DEFAULT_SEED) {
+            expect("hash", len, seed, hash_engine_hash(data, len), h64);
+        }
+        expect("hash_seeded", len, seed, hash_engine_hash_seeded(data, len, seed), h64);
+
+        HashEngineDigest128 one_shot = hash_engine_hash128(data, len, seed);
+        expect("hash128.hi", len, seed, one_shot.hi, hi);
+        expect("hash128.lo", len, seed, one_shot.lo, lo);
+
+        HashEngineState *state = hash_engine_new(seed);
+        for (size_t pos = 0; pos < len; pos += STREAM_CHUNK) {
+            size_t take = len - pos < STREAM_CHUNK ? len - pos : STREAM_CHUNK;
+            hash_engine_update(state, input + pos, take);
+        }
+        expect("finish", len, seed, hash_engine_finish(state), h64);
+
+        HashEngineDigest128 streamed = hash_engine_finish128(state);
+        expect("finish128.hi", len, seed, streamed.hi, hi);
+        expect("finish128.lo", len, seed, streamed.lo, lo);
+        hash_engine_free(state);
+    }
+
+    fclose(file);
+    hash_engine_free(NULL);
+
+    printf("%d vectors, %d failures\n", vectors, failures);
+    return failures == 0 ? 0 : 1;
+}
@@ -1,12 +1,34 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::Hasher;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
-use hash::core::domain::hash_engine::HashEngine;
+use hash::constants::DEFAULT_SEED;
+use hash::core::domain::{
+    baseline::{fnv::Fnv1aHasher, fx::FxHasher, sip::SipHasher13, xxhash::XxHash64},
+    hash_bytes::hash_slice,
+    hash_engine::HashEngine,
+};
 
 const WARMUP_ITERS: usize = 100;
-const THROUGHPUT_ITERS: usize = 10;
-const LATENCY_ITERS: usize = 500_000;
+const THROUGHPUT_SAMPLES: usize = 15;
+const THROUGHPUT_ITERS: usize = 3;
+const LATENCY_SAMPLES: usize = 50;
+const LATENCY_ITERS: usize = 10_000;
+
+// Tukey fences: samples further than this many IQRs outside the
+// quartiles are treated as scheduler or frequency noise and dropped.
+const OUTLIER_IQR_FACTOR: f64 = 1.5;
+
+type BenchFn = fn(&[u8], usize, usize) -> Stats;
+
+const HASHERS: &[(&str, BenchFn)] = &[
+    ("HashEngine", bench::<HashEngine>),
+    ("DefaultHasher", bench::<DefaultHasher>),
+    ("FNV-1a", bench::<Fnv1aHasher>),
+    ("FxHash", bench::<FxHasher>),
+    ("SipHash-1-3", bench::<SipHasher13>),
+    ("xxHash64", bench::<XxHash64>),
+];
 
 const THROUGHPUT_SIZES: &[(usize, &str)] = &[
     (1_000_000, "1 MB"),
@@ -14,7 +36,33 @@ const THROUGHPUT_SIZES: &[(usize, &str)] = &[
     (100_000_000, "100 MB"),
 ];
 
-const LATENCY_SIZES: &[(usize, &str)] = &[(8, "8 B"), (32, "32 B"), (256, "256 B"), (1024, "1 KB")];
+const POD_ELEMENTS: usize = 1_000_000;
+
+const LATENCY_SIZES: &[(usize, &str)] = &[
+    (1, "1 B"),
+    (4, "4 B"),
+    (8, "8 B"),
+    (16, "16 B"),
+    (32, "32 B"),
+    (256, "256 B"),
+    (1024, "1 KB"),
+];
+
+struct Sample {
+    nanos: f64,
+    cycles: Option<f64>,
+}
+
+struct Stats {
+    median: f64,
+    mean: f64,
+    stddev: f64,
+    p5: f64,
+    p95: f64,
+    cycles: Option<f64>,
+    kept: usize,
+    rejected: usize,
+}
 
 fn generate_data(size: usize) -> Vec<u8> {
     let mut data = vec![0u8; size];
@@ -30,65 +78,280 @@ fn generate_data(size: usize) -> Vec<u8> {
     data
 }
 
-fn bench<H: Hasher + Default>(data: &[u8], iters: usize) -> Duration {
-    for _ in 0..WARMUP_ITERS {
-        let mut h = H::default();
-        h.write(data);
-        std::hint::black_box(h.finish());
+#[cfg(target_os = "linux")]
+fn pin_to_cpu(cpu: usize) -> bool {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(cpu, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
     }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_to_cpu(_cpu: usize) -> bool {
+    false
+}
+
+#[cfg(target_arch = "x86_64")]
+fn read_cycles() -> Option<u64> {
+    Some(unsafe { std::arch::x86_64::_rdtsc() })
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn read_cycles() -> Option<u64> {
+    None
+}
+
+fn run_once<H: Hasher + Default>(data: &[u8]) {
+    let mut h = H::default();
+    h.write(std::hint::black_box(data));
+    std::hint::black_box(h.finish());
+}
 
+fn sample(iters: usize, run: &mut impl FnMut()) -> Sample {
+    let cycles_start = read_cycles();
     let start = Instant::now();
     for _ in 0..iters {
-        let mut h = H::default();
-        h.write(data);
-        std::hint::black_box(h.finish());
+        run();
+    }
+    let elapsed = start.elapsed();
+    let cycles_end = read_cycles();
+
+    let cycles = match (cycles_start, cycles_end) {
+        (Some(s), Some(e)) => Some(e.wrapping_sub(s) as f64 / iters as f64),
+        _ => None,
+    };
+
+    Sample {
+        nanos: elapsed.as_nanos() as f64 / iters as f64,
+        cycles,
     }
-    start.elapsed() / iters as u32
 }
 
-fn throughput_gbps(duration: Duration, size: usize) -> f64 {
-    size as f64 / duration.as_secs_f64() / 1_000_000_000.0
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+fn summarize(samples: &[Sample]) -> Stats {
+    let mut all: Vec<f64> = samples.iter().map(|s| s.nanos).collect();
+    all.sort_by(f64::total_cmp);
+
+    let q1 = percentile(&all, 25.0);
+    let q3 = percentile(&all, 75.0);
+    let iqr = q3 - q1;
+    let lo = q1 - OUTLIER_IQR_FACTOR * iqr;
+    let hi = q3 + OUTLIER_IQR_FACTOR * iqr;
+
+    let kept: Vec<&Sample> = samples
+        .iter()
+        .filter(|s| s.nanos >= lo && s.nanos <= hi)
+        .collect();
+
+    let mut nanos: Vec<f64> = kept.iter().map(|s| s.nanos).collect();
+    nanos.sort_by(f64::total_cmp);
+
+    let n = nanos.len() as f64;
+    let mean = nanos.iter().sum::<f64>() / n;
+    let variance = nanos.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0).max(1.0);
+
+    let mut cycles: Vec<f64> = kept.iter().filter_map(|s| s.cycles).collect();
+    cycles.sort_by(f64::total_cmp);
+    let cycles = (!cycles.is_empty()).then(|| percentile(&cycles, 50.0));
+
+    Stats {
+        median: percentile(&nanos, 50.0),
+        mean,
+        stddev: variance.sqrt(),
+        p5: percentile(&nanos, 5.0),
+        p95: percentile(&nanos, 95.0),
+        cycles,
+        kept: nanos.len(),
+        rejected: samples.len() - nanos.len(),
+    }
+}
+
+fn measure(samples: usize, iters: usize, mut run: impl FnMut()) -> Stats {
+    for _ in 0..WARMUP_ITERS {
+        run();
+    }
+
+    let samples: Vec<Sample> = (0..samples).map(|_| sample(iters, &mut run)).collect();
+    summarize(&samples)
+}
+
+fn bench<H: Hasher + Default>(data: &[u8], samples: usize, iters: usize) -> Stats {
+    measure(samples, iters, || run_once::<H>(data))
+}
+
+// The one-shot path, timed the same way as a `Hasher` for the latency table.
+fn bench_one_shot(data: &[u8], samples: usize, iters: usize) -> Stats {
+    measure(samples, iters, || {
+        std::hint::black_box(hash::hash_oneshot(std::hint::black_box(data), DEFAULT_SEED));
+    })
+}
+
+fn bench_pod(label: &str, size: usize, per_element: impl FnMut(), whole: impl FnMut()) {
+    let ours = measure(THROUGHPUT_SAMPLES, THROUGHPUT_ITERS, whole);
+    let theirs = measure(THROUGHPUT_SAMPLES, THROUGHPUT_ITERS, per_element);
+
+    print_throughput_row(label, "per-element", &theirs, size);
+    print_throughput_row(label, "hash_slice", &ours, size);
+    println!(
+        "{:<10} {:<14} {:>9.2}x",
+        label,
+        "speedup",
+        theirs.median / ours.median
+    );
+}
+
+fn throughput_gbps(nanos: f64, size: usize) -> f64 {
+    size as f64 / nanos
+}
+
+fn cycles_per_byte(stats: &Stats, size: usize) -> String {
+    match stats.cycles {
+        Some(c) => format!("{:.3}", c / size as f64),
+        None => "n/a".to_string(),
+    }
+}
+
+fn print_throughput_row(label: &str, name: &str, stats: &Stats, size: usize) {
+    println!(
+        "{:<10} {:<14} {:>9.3} {:>9.3} {:>9.3} {:>8.2}% {:>8} {:>5}/{}",
+        label,
+        name,
+        throughput_gbps(stats.median, size),
+        throughput_gbps(stats.p95, size),
+        throughput_gbps(stats.p5, size),
+        stats.stddev / stats.mean * 100.0,
+        cycles_per_byte(stats, size),
+        stats.kept,
+        stats.kept + stats.rejected,
+    );
+}
+
+fn print_latency_row(label: &str, name: &str, stats: &Stats) {
+    let cycles = match stats.cycles {
+        Some(c) => format!("{:.1}", c),
+        None => "n/a".to_string(),
+    };
+    println!(
+        "{:<10} {:<14} {:>9.1} {:>9.1} {:>9.1} {:>9.2} {:>8} {:>5}/{}",
+        label,
+        name,
+        stats.median,
+        stats.p5,
+        stats.p95,
+        stats.stddev,
+        cycles,
+        stats.kept,
+        stats.kept + stats.rejected,
+    );
 }
 
 fn main() {
+    let cpu = std::env::var("BENCH_CPU")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+
     println!();
-    println!("=== THROUGHPUT (GB/s) ===");
+    if pin_to_cpu(cpu) {
+        println!("Pinned to CPU {}", cpu);
+    } else {
+        println!("CPU pinning unavailable, results may be noisier");
+    }
+
+    println!();
+    println!(
+        "=== THROUGHPUT (GB/s, median of {} samples) ===",
+        THROUGHPUT_SAMPLES
+    );
     println!(
-        "{:<10} {:>14} {:>14}",
-        "Size", "HashEngine", "DefaultHasher"
+        "{:<10} {:<14} {:>9} {:>9} {:>9} {:>9} {:>8} {:>7}",
+        "Size", "Hasher", "Median", "P5", "P95", "RSD", "Cyc/B", "Kept"
     );
 
     for &(size, label) in THROUGHPUT_SIZES {
         let data = generate_data(size);
-        let ours = bench::<HashEngine>(&data, THROUGHPUT_ITERS);
-        let theirs = bench::<DefaultHasher>(&data, THROUGHPUT_ITERS);
-
-        println!(
-            "{:<10} {:>11.3} GB/s {:>11.3} GB/s",
-            label,
-            throughput_gbps(ours, size),
-            throughput_gbps(theirs, size),
-        );
+        for &(name, run) in HASHERS {
+            let stats = run(&data, THROUGHPUT_SAMPLES, THROUGHPUT_ITERS);
+            print_throughput_row(label, name, &stats, size);
+        }
     }
 
     println!();
-    println!("=== LATENCY (ns/op) ===");
+    println!("=== NUMERIC SLICES (GB/s, {} elements) ===", POD_ELEMENTS);
+    println!(
+        "{:<10} {:<14} {:>9} {:>9} {:>9} {:>9} {:>8} {:>7}",
+        "Type", "Path", "Median", "P5", "P95", "RSD", "Cyc/B", "Kept"
+    );
+
+    let bytes = generate_data(POD_ELEMENTS * 8);
+    let words: Vec<u32> = bytes[..POD_ELEMENTS * 4]
+        .chunks_exact(4)
+        .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+        .collect();
+    let floats: Vec<f64> = bytes
+        .chunks_exact(8)
+        .map(|c| u64::from_le_bytes(c.try_into().unwrap()) as f64)
+        .collect();
+
+    bench_pod(
+        "u32",
+        POD_ELEMENTS * 4,
+        || {
+            let mut h = HashEngine::default();
+            for &x in std::hint::black_box(&words) {
+                h.write_u32(x);
+            }
+            std::hint::black_box(h.finish());
+        },
+        || {
+            let mut h = HashEngine::default();
+            hash_slice(std::hint::black_box(&words), &mut h);
+            std::hint::black_box(h.finish());
+        },
+    );
+    bench_pod(
+        "f64",
+        POD_ELEMENTS * 8,
+        || {
+            let mut h = HashEngine::default();
+            for &x in std::hint::black_box(&floats) {
+                h.write_u64(x.to_bits());
+            }
+            std::hint::black_box(h.finish());
+        },
+        || {
+            let mut h = HashEngine::default();
+            hash_slice(std::hint::black_box(&floats), &mut h);
+            std::hint::black_box(h.finish());
+        },
+    );
+
+    println!();
+    println!(
+        "=== LATENCY (ns/op, median of {} samples) ===",
+        LATENCY_SAMPLES
+    );
     println!(
-        "{:<10} {:>14} {:>14}",
-        "Size", "HashEngine", "DefaultHasher"
+        "{:<10} {:<14} {:>9} {:>9} {:>9} {:>9} {:>8} {:>7}",
+        "Size", "Hasher", "Median", "P5", "P95", "StdDev", "Cycles", "Kept"
     );
 
     for &(size, label) in LATENCY_SIZES {
         let data = generate_data(size);
-        let ours = bench::<HashEngine>(&data, LATENCY_ITERS);
-        let theirs = bench::<DefaultHasher>(&data, LATENCY_ITERS);
-
-        println!(
-            "{:<10} {:>11} ns {:>11} ns",
-            label,
-            ours.as_nanos(),
-            theirs.as_nanos(),
-        );
+        for &(name, run) in HASHERS {
+            let stats = run(&data, LATENCY_SAMPLES, LATENCY_ITERS);
+            print_latency_row(label, name, &stats);
+        }
+        let stats = bench_one_shot(&data, LATENCY_SAMPLES, LATENCY_ITERS);
+        print_latency_row(label, "hash_oneshot", &stats);
     }
 
     println!();
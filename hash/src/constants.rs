@@ -3,3 +3,4 @@ pub const PRIME_MIX: u64 = 0xC2B2AE3D27D4EB4F;
 pub const PRIME_LANE: u64 = 0x165667B19E3779F9;
 pub const PRIME_FINAL: u64 = 0x85EBCA77C2B2AE63;
 pub const PRIME_TAIL: u64 = 0x27D4EB2F165667C5;
+pub const DEFAULT_SEED: u64 = 0xcafecafecafecafe;
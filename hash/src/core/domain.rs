@@ -1 +1,12 @@
-pub mod hash_engine;
\ No newline at end of file
+pub mod baseline;
+pub mod combinable;
+pub mod const_engine;
+pub mod digest;
+#[cfg(feature = "std")]
+pub mod fs_tree;
+pub mod hash_bytes;
+pub mod hash_engine;
+#[cfg(feature = "alloc")]
+pub mod merkle;
+pub mod stable_hash;
+pub mod xof;
@@ -0,0 +1,4 @@
+pub mod fnv;
+pub mod fx;
+pub mod sip;
+pub mod xxhash;
@@ -0,0 +1,42 @@
+use core::hash::Hasher;
+
+use crate::shared::helper::{load_u32, load_u64};
+
+const SEED: u64 = 0x517cc1b727220a95;
+
+// Same word-at-a-time mixing as rustc's FxHasher.
+#[derive(Default)]
+pub struct FxHasher {
+    state: u64,
+}
+
+impl FxHasher {
+    fn add_to_hash(&mut self, word: u64) {
+        self.state = (self.state.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let (chunks, mut tail) = bytes.as_chunks::<8>();
+        for chunk in chunks {
+            self.add_to_hash(load_u64(chunk));
+        }
+
+        if let Some((word, rest)) = tail.split_first_chunk::<4>() {
+            self.add_to_hash(load_u32(word) as u64);
+            tail = rest;
+        }
+        if tail.len() >= 2 {
+            self.add_to_hash(u16::from_le_bytes([tail[0], tail[1]]) as u64);
+            tail = &tail[2..];
+        }
+        if let Some(&byte) = tail.first() {
+            self.add_to_hash(byte as u64);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
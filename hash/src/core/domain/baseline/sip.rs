@@ -0,0 +1,104 @@
+use core::hash::Hasher;
+
+use crate::shared::helper::load_u64;
+
+// SipHash-1-3 with a zero key, which is what `DefaultHasher::new()` runs today.
+#[derive(Clone, Copy)]
+pub struct SipHasher13 {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    tail: [u8; 8],
+    tail_len: usize,
+    length: usize,
+}
+
+impl SipHasher13 {
+    pub fn new_with_keys(k0: u64, k1: u64) -> Self {
+        Self {
+            v0: k0 ^ 0x736f6d6570736575,
+            v1: k1 ^ 0x646f72616e646f6d,
+            v2: k0 ^ 0x6c7967656e657261,
+            v3: k1 ^ 0x7465646279746573,
+            tail: [0; 8],
+            tail_len: 0,
+            length: 0,
+        }
+    }
+
+    fn sip_round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13);
+        self.v1 ^= self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16);
+        self.v3 ^= self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21);
+        self.v3 ^= self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17);
+        self.v1 ^= self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    fn compress(&mut self, m: u64) {
+        self.v3 ^= m;
+        self.sip_round();
+        self.v0 ^= m;
+    }
+}
+
+impl Default for SipHasher13 {
+    fn default() -> Self {
+        Self::new_with_keys(0, 0)
+    }
+}
+
+impl Hasher for SipHasher13 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.length += bytes.len();
+        let mut offset = 0;
+
+        if self.tail_len > 0 {
+            let take = (8 - self.tail_len).min(bytes.len());
+            self.tail[self.tail_len..self.tail_len + take].copy_from_slice(&bytes[..take]);
+            self.tail_len += take;
+            offset += take;
+
+            if self.tail_len < 8 {
+                return;
+            }
+
+            self.compress(u64::from_le_bytes(self.tail));
+            self.tail_len = 0;
+        }
+
+        let (chunks, remainder) = bytes[offset..].as_chunks::<8>();
+
+        for chunk in chunks {
+            self.compress(load_u64(chunk));
+        }
+
+        self.tail_len = remainder.len();
+        self.tail[..self.tail_len].copy_from_slice(remainder);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = *self;
+
+        let mut last = [0u8; 8];
+        last[..self.tail_len].copy_from_slice(&self.tail[..self.tail_len]);
+        let b = ((self.length as u64 & 0xff) << 56) | u64::from_le_bytes(last);
+
+        state.compress(b);
+        state.v2 ^= 0xff;
+        state.sip_round();
+        state.sip_round();
+        state.sip_round();
+
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+}
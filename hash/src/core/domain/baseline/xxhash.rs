@@ -0,0 +1,145 @@
+use core::hash::Hasher;
+
+use crate::shared::helper::{load_u32, load_u64};
+
+const PRIME64_1: u64 = 0x9E3779B185EBCA87;
+const PRIME64_2: u64 = 0xC2B2AE3D27D4EB4F;
+const PRIME64_3: u64 = 0x165667B19E3779F9;
+const PRIME64_4: u64 = 0x85EBCA77C2B2AE63;
+const PRIME64_5: u64 = 0x27D4EB2F165667C5;
+
+fn round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+fn merge_round(acc: u64, val: u64) -> u64 {
+    (acc ^ round(0, val))
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(PRIME64_4)
+}
+
+// Reference XXH64, scalar and unseeded, so the SIMD engine has a
+// straightforward relative to be measured against.
+pub struct XxHash64 {
+    seed: u64,
+    lanes: [u64; 4],
+    buffer: [u8; 32],
+    buffer_len: usize,
+    total_len: usize,
+}
+
+impl XxHash64 {
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            lanes: [
+                seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2),
+                seed.wrapping_add(PRIME64_2),
+                seed,
+                seed.wrapping_sub(PRIME64_1),
+            ],
+            buffer: [0; 32],
+            buffer_len: 0,
+            total_len: 0,
+        }
+    }
+
+    fn consume(&mut self, stripe: &[u8; 32]) {
+        let (words, _) = stripe.as_chunks::<8>();
+        for (lane, word) in self.lanes.iter_mut().zip(words) {
+            *lane = round(*lane, load_u64(word));
+        }
+    }
+}
+
+impl Default for XxHash64 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Hasher for XxHash64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.total_len += bytes.len();
+        let mut offset = 0;
+
+        if self.buffer_len > 0 {
+            let take = (32 - self.buffer_len).min(bytes.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&bytes[..take]);
+            self.buffer_len += take;
+            offset += take;
+
+            if self.buffer_len < 32 {
+                return;
+            }
+
+            let stripe = self.buffer;
+            self.consume(&stripe);
+            self.buffer_len = 0;
+        }
+
+        let (chunks, remainder) = bytes[offset..].as_chunks::<32>();
+
+        for chunk in chunks {
+            self.consume(chunk);
+        }
+
+        self.buffer_len = remainder.len();
+        self.buffer[..self.buffer_len].copy_from_slice(remainder);
+    }
+
+    fn finish(&self) -> u64 {
+        let [v1, v2, v3, v4] = self.lanes;
+
+        let mut state = if self.total_len >= 32 {
+            let mut acc = v1
+                .rotate_left(1)
+                .wrapping_add(v2.rotate_left(7))
+                .wrapping_add(v3.rotate_left(12))
+                .wrapping_add(v4.rotate_left(18));
+            for lane in self.lanes {
+                acc = merge_round(acc, lane);
+            }
+            acc
+        } else {
+            self.seed.wrapping_add(PRIME64_5)
+        };
+
+        state = state.wrapping_add(self.total_len as u64);
+
+        let mut buf = &self.buffer[..self.buffer_len];
+
+        while let Some((word, rest)) = buf.split_first_chunk::<8>() {
+            state ^= round(0, load_u64(word));
+            state = state
+                .rotate_left(27)
+                .wrapping_mul(PRIME64_1)
+                .wrapping_add(PRIME64_4);
+            buf = rest;
+        }
+
+        if let Some((word, rest)) = buf.split_first_chunk::<4>() {
+            state ^= (load_u32(word) as u64).wrapping_mul(PRIME64_1);
+            state = state
+                .rotate_left(23)
+                .wrapping_mul(PRIME64_2)
+                .wrapping_add(PRIME64_3);
+            buf = rest;
+        }
+
+        for &byte in buf {
+            state ^= (byte as u64).wrapping_mul(PRIME64_5);
+            state = state.rotate_left(11).wrapping_mul(PRIME64_1);
+        }
+
+        state ^= state >> 33;
+        state = state.wrapping_mul(PRIME64_2);
+        state ^= state >> 29;
+        state = state.wrapping_mul(PRIME64_3);
+        state ^= state >> 32;
+
+        state
+    }
+}
@@ -0,0 +1,103 @@
+use core::hash::Hasher;
+
+// Polynomial hash over the Mersenne prime 2^61 - 1, kept apart from
+// `HashEngine` because it is linear by design: for byte strings A and B,
+//
+//   h(A || B) = h(A) * BASE^len(B) + h(B)   (mod 2^61 - 1)
+//
+// so segments hashed independently (on different machines, in any order) can
+// be joined with `combine` without touching the bytes again. Each byte `b`
+// contributes `b + 1`, so leading zero bytes still change the value.
+//
+// The linearity that makes this useful also makes collisions easy to
+// construct on purpose; do not use it for untrusted keys in hash tables.
+const MODULUS: u64 = (1 << 61) - 1;
+const BASE: u64 = 0x0f1e_2d3c_4b5a_6978 % MODULUS;
+
+#[derive(Clone, Copy, Default)]
+pub struct CombinableHasher {
+    state: u64,
+    len: u64,
+}
+
+impl CombinableHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Hasher for CombinableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        let base8 = pow_mod(BASE, 8);
+
+        for chunk in &mut chunks {
+            let mut block = 0;
+            for &byte in chunk {
+                block = add_mod(mul_mod(block, BASE), byte as u64 + 1);
+            }
+            self.state = add_mod(mul_mod(self.state, base8), block);
+        }
+
+        for &byte in chunks.remainder() {
+            self.state = add_mod(mul_mod(self.state, BASE), byte as u64 + 1);
+        }
+
+        self.len += bytes.len() as u64;
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+pub fn combinable_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = CombinableHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+// Hash of `A || B` from `h(A)`, `h(B)` and the length of `B` in bytes.
+pub fn combine(left: u64, right: u64, right_len: u64) -> u64 {
+    add_mod(mul_mod(left, pow_mod(BASE, right_len)), right)
+}
+
+fn add_mod(a: u64, b: u64) -> u64 {
+    let sum = a + b;
+    if sum >= MODULUS { sum - MODULUS } else { sum }
+}
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    let product = a as u128 * b as u128;
+    reduce((product as u64 & MODULUS) + (product >> 61) as u64)
+}
+
+// Any `u64` folds to at most `MODULUS + 7`, so one subtraction is enough.
+fn reduce(x: u64) -> u64 {
+    let folded = (x & MODULUS) + (x >> 61);
+    if folded >= MODULUS {
+        folded - MODULUS
+    } else {
+        folded
+    }
+}
+
+fn pow_mod(mut base: u64, mut exp: u64) -> u64 {
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base);
+        }
+        base = mul_mod(base, base);
+        exp >>= 1;
+    }
+    result
+}
@@ -0,0 +1,109 @@
+use crate::{
+    constants::{DEFAULT_SEED, PRIME_DIFFUSE, PRIME_FINAL, PRIME_LANE, PRIME_MIX, PRIME_TAIL},
+    shared::helper::{avalanche, round},
+};
+
+// Scalar mirror of `HashEngine` that can run in const context.
+// `hash_const(bytes, seed)` must equal `HashEngine::new(seed)` fed with
+// `write(bytes)` followed by `finish()`, for any split of the writes.
+
+const fn read_u64(bytes: &[u8], pos: usize) -> u64 {
+    u64::from_le_bytes([
+        bytes[pos],
+        bytes[pos + 1],
+        bytes[pos + 2],
+        bytes[pos + 3],
+        bytes[pos + 4],
+        bytes[pos + 5],
+        bytes[pos + 6],
+        bytes[pos + 7],
+    ])
+}
+
+const fn read_u32(bytes: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]])
+}
+
+pub const fn hash_const(bytes: &[u8], seed: u64) -> u64 {
+    let mut acc = [
+        seed.wrapping_add(PRIME_DIFFUSE).wrapping_add(PRIME_MIX),
+        seed.wrapping_add(PRIME_MIX),
+        seed,
+        seed.wrapping_add(PRIME_DIFFUSE),
+    ];
+
+    let len = bytes.len();
+    let mut pos = 0;
+
+    while pos + 32 <= len {
+        acc[0] = round(acc[0], read_u64(bytes, pos));
+        acc[1] = round(acc[1], read_u64(bytes, pos + 8));
+        acc[2] = round(acc[2], read_u64(bytes, pos + 16));
+        acc[3] = round(acc[3], read_u64(bytes, pos + 24));
+        pos += 32;
+    }
+
+    let mut state: u64;
+
+    if len >= 32 {
+        state = acc[0]
+            .rotate_left(1)
+            .wrapping_add(acc[1].rotate_left(7))
+            .wrapping_add(acc[2].rotate_left(12))
+            .wrapping_add(acc[3].rotate_left(18));
+
+        let mut i = 0;
+        while i < 4 {
+            state ^= round(0, acc[i]);
+            state = state.wrapping_mul(PRIME_DIFFUSE).wrapping_add(PRIME_FINAL);
+            i += 1;
+        }
+    } else {
+        state = acc[2].wrapping_add(PRIME_TAIL);
+    }
+
+    state = state.wrapping_add(len as u64);
+
+    while pos + 8 <= len {
+        state ^= round(0, read_u64(bytes, pos));
+        state = state
+            .rotate_left(27)
+            .wrapping_mul(PRIME_DIFFUSE)
+            .wrapping_add(PRIME_FINAL);
+        pos += 8;
+    }
+
+    while pos + 4 <= len {
+        let k = read_u32(bytes, pos) as u64;
+        state ^= k.wrapping_mul(PRIME_DIFFUSE);
+        state = state
+            .rotate_left(23)
+            .wrapping_mul(PRIME_MIX)
+            .wrapping_add(PRIME_LANE);
+        pos += 4;
+    }
+
+    while pos < len {
+        state ^= (bytes[pos] as u64).wrapping_mul(PRIME_TAIL);
+        state = state.rotate_left(11).wrapping_mul(PRIME_DIFFUSE);
+        pos += 1;
+    }
+
+    avalanche(state)
+}
+
+pub const fn hash_str(value: &str) -> u64 {
+    hash_const(value.as_bytes(), DEFAULT_SEED)
+}
+
+// Evaluates `hash_const` at compile time, e.g. `const USERS: u64 = hash!("users");`
+// so hashed identifiers can be matched against as constants.
+#[macro_export]
+macro_rules! hash {
+    ($value:expr) => {
+        const { $crate::core::domain::const_engine::hash_str($value) }
+    };
+    ($value:expr, $seed:expr) => {
+        const { $crate::core::domain::const_engine::hash_const($value.as_bytes(), $seed) }
+    };
+}
@@ -0,0 +1,190 @@
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use crate::shared::encoding::{
+    DecodeError, decode_base32, decode_base64, decode_hex, write_base32, write_base64, write_hex,
+};
+
+// A hash value as a fixed-size big-endian byte string, so the hex form of a
+// `Digest64` reads the same as `{:016x}` of the `u64` it came from. `Digest128`
+// starts with the same eight bytes as the `Digest64` of the same input.
+//
+// Equality is constant-time over all bytes so digests can be compared against
+// secrets (e.g. keyed MACs) without leaking the position of the first
+// mismatch. Ordering is plain lexicographic and not constant-time.
+#[derive(Clone, Copy)]
+pub struct Digest<const N: usize>([u8; N]);
+
+pub type Digest64 = Digest<8>;
+pub type Digest128 = Digest<16>;
+
+pub struct Base32<'a, const N: usize>(&'a Digest<N>);
+
+pub struct Base64<'a, const N: usize>(&'a Digest<N>);
+
+impl<const N: usize> Digest<N> {
+    pub const fn from_bytes(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+
+    pub const fn to_bytes(self) -> [u8; N] {
+        self.0
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    pub fn from_hex(input: &str) -> Result<Self, DecodeError> {
+        let mut bytes = [0u8; N];
+        decode_hex(input, &mut bytes)?;
+        Ok(Self(bytes))
+    }
+
+    pub fn from_base32(input: &str) -> Result<Self, DecodeError> {
+        let mut bytes = [0u8; N];
+        decode_base32(input, &mut bytes)?;
+        Ok(Self(bytes))
+    }
+
+    pub fn from_base64(input: &str) -> Result<Self, DecodeError> {
+        let mut bytes = [0u8; N];
+        decode_base64(input, &mut bytes)?;
+        Ok(Self(bytes))
+    }
+
+    // Display adaptors, e.g. `format!("{}", digest.base32())`.
+    pub fn base32(&self) -> Base32<'_, N> {
+        Base32(self)
+    }
+
+    pub fn base64(&self) -> Base64<'_, N> {
+        Base64(self)
+    }
+}
+
+impl Digest64 {
+    pub const fn as_u64(&self) -> u64 {
+        u64::from_be_bytes(self.0)
+    }
+}
+
+impl Digest128 {
+    pub const fn as_u128(&self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+}
+
+impl From<u64> for Digest64 {
+    fn from(value: u64) -> Self {
+        Self(value.to_be_bytes())
+    }
+}
+
+impl From<Digest64> for u64 {
+    fn from(digest: Digest64) -> Self {
+        digest.as_u64()
+    }
+}
+
+impl From<u128> for Digest128 {
+    fn from(value: u128) -> Self {
+        Self(value.to_be_bytes())
+    }
+}
+
+impl From<Digest128> for u128 {
+    fn from(digest: Digest128) -> Self {
+        digest.as_u128()
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Digest<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8]> for Digest<N> {
+    type Error = DecodeError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| DecodeError::InvalidLength)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Digest<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> PartialEq for Digest<N> {
+    fn eq(&self, other: &Self) -> bool {
+        let diff = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        core::hint::black_box(diff) == 0
+    }
+}
+
+impl<const N: usize> Eq for Digest<N> {}
+
+impl<const N: usize> PartialOrd for Digest<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for Digest<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<const N: usize> Hash for Digest<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<const N: usize> fmt::Display for Digest<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hex(f, &self.0)
+    }
+}
+
+impl<const N: usize> fmt::Debug for Digest<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Digest{}({})", N * 8, self)
+    }
+}
+
+impl<const N: usize> FromStr for Digest<N> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
+impl<const N: usize> fmt::Display for Base32<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_base32(f, &self.0.0)
+    }
+}
+
+impl<const N: usize> fmt::Display for Base64<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_base64(f, &self.0.0)
+    }
+}
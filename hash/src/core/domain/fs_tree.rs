@@ -0,0 +1,165 @@
+use core::hash::Hasher;
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, Metadata},
+    io,
+    path::{Path, PathBuf},
+    vec::Vec,
+};
+
+use super::{digest::Digest64, hash_engine::HashEngine};
+use crate::shared::io::hash_reader;
+
+const TAG_FILE: u8 = b'f';
+const TAG_DIR: u8 = b'd';
+const TAG_SYMLINK: u8 = b'l';
+const TAG_OTHER: u8 = b'o';
+
+// Fingerprints a directory tree independently of traversal order, timestamps
+// and ownership. Each directory hashes its entries sorted by raw name bytes,
+// where every entry is encoded as:
+//
+//   tag (1 byte: f, d, l or o) | name length (u64 LE) | name bytes | mode (u32 LE)
+//
+// followed by, for files, the size (u64 LE) and the content `Digest64`; for
+// directories, the subtree `Digest64`; and for symlinks, the target length (u64 LE)
+// and target bytes. Symlinks are recorded, never followed. Everything else
+// (FIFOs, sockets, device nodes) is tagged `o` and encoded by name and mode
+// only: it is never opened, since reading it may block or never end. `mode`
+// holds the Unix permission bits, or just the read-only bit on other platforms.
+pub fn hash_tree(root: &Path) -> io::Result<Digest64> {
+    let metadata = fs::symlink_metadata(root)?;
+    let mut hasher = HashEngine::default();
+    write_entry(&mut hasher, root, b"", &metadata)?;
+    Ok(hasher.finish_digest())
+}
+
+pub fn hash_file(path: &Path) -> io::Result<Digest64> {
+    hash_reader(&mut File::open(path)?, crate::constants::DEFAULT_SEED).map(Digest64::from)
+}
+
+// Returns sets of files with identical contents, largest files first. Files are
+// bucketed by size and only same-size candidates are read and hashed. Empty
+// files and symlinks are ignored.
+pub fn find_duplicates(root: &Path) -> io::Result<Vec<Vec<PathBuf>>> {
+    let mut by_size: BTreeMap<u64, Vec<PathBuf>> = BTreeMap::new();
+    collect_files(root, &mut by_size)?;
+
+    let mut sets = Vec::new();
+
+    for (_, candidates) in by_size.into_iter().rev() {
+        if candidates.len() < 2 {
+            continue;
+        }
+
+        let mut by_hash: BTreeMap<Digest64, Vec<PathBuf>> = BTreeMap::new();
+        for path in candidates {
+            by_hash.entry(hash_file(&path)?).or_default().push(path);
+        }
+
+        for (_, mut paths) in by_hash {
+            if paths.len() > 1 {
+                paths.sort();
+                sets.push(paths);
+            }
+        }
+    }
+
+    Ok(sets)
+}
+
+fn write_entry(
+    hasher: &mut HashEngine,
+    path: &Path,
+    name: &[u8],
+    metadata: &Metadata,
+) -> io::Result<()> {
+    let file_type = metadata.file_type();
+    let tag = if file_type.is_symlink() {
+        TAG_SYMLINK
+    } else if file_type.is_dir() {
+        TAG_DIR
+    } else if file_type.is_file() {
+        TAG_FILE
+    } else {
+        TAG_OTHER
+    };
+
+    hasher.write(&[tag]);
+    write_bytes(hasher, name);
+    hasher.write(&mode(metadata).to_le_bytes());
+
+    match tag {
+        TAG_SYMLINK => write_bytes(hasher, &name_bytes(fs::read_link(path)?.as_os_str())),
+        TAG_DIR => hasher.write(hash_dir(path)?.as_bytes()),
+        TAG_FILE => {
+            hasher.write(&metadata.len().to_le_bytes());
+            hasher.write(hash_file(path)?.as_bytes());
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn hash_dir(path: &Path) -> io::Result<Digest64> {
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| (name_bytes(&e.file_name()), e.path())))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    let mut hasher = HashEngine::default();
+    hasher.write(&(entries.len() as u64).to_le_bytes());
+
+    for (name, path) in entries {
+        let metadata = fs::symlink_metadata(&path)?;
+        write_entry(&mut hasher, &path, &name, &metadata)?;
+    }
+
+    Ok(hasher.finish_digest())
+}
+
+fn collect_files(path: &Path, out: &mut BTreeMap<u64, Vec<PathBuf>>) -> io::Result<()> {
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            collect_files(&entry.path(), out)?;
+        } else if file_type.is_file() {
+            let len = entry.metadata()?.len();
+            if len > 0 {
+                out.entry(len).or_default().push(entry.path());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_bytes(hasher: &mut HashEngine, bytes: &[u8]) {
+    hasher.write(&(bytes.len() as u64).to_le_bytes());
+    hasher.write(bytes);
+}
+
+#[cfg(unix)]
+fn name_bytes(name: &std::ffi::OsStr) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    name.as_bytes().to_vec()
+}
+
+#[cfg(not(unix))]
+fn name_bytes(name: &std::ffi::OsStr) -> Vec<u8> {
+    name.to_string_lossy().as_bytes().to_vec()
+}
+
+#[cfg(unix)]
+fn mode(metadata: &Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn mode(metadata: &Metadata) -> u32 {
+    metadata.permissions().readonly() as u32
+}
@@ -0,0 +1,47 @@
+use core::hash::Hasher;
+
+use super::hash_engine::HashEngine;
+
+/// Marker for plain-old-data types whose in-memory representation is exactly
+/// their value: no padding, no pointers, and every byte initialized. A slice of
+/// such values can be hashed as one contiguous byte range.
+///
+/// The bytes are native-endian, and `usize`/`isize` follow the target width, so
+/// these hashes only agree between machines with the same layout. Use
+/// `StableHash` when the result has to be persisted.
+///
+/// # Safety
+///
+/// Implementors must have no padding bytes and no interior mutability.
+pub unsafe trait HashBytes: Copy {}
+
+macro_rules! impl_hash_bytes {
+    ($($ty:ty),* $(,)?) => {
+        $(unsafe impl HashBytes for $ty {})*
+    };
+}
+
+impl_hash_bytes! {
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64, bool, char,
+}
+
+unsafe impl<T: HashBytes, const N: usize> HashBytes for [T; N] {}
+
+pub fn as_bytes<T: HashBytes>(data: &[T]) -> &[u8] {
+    // SAFETY: `HashBytes` guarantees every byte of `T` is initialized.
+    unsafe { core::slice::from_raw_parts(data.as_ptr() as *const u8, size_of_val(data)) }
+}
+
+// Feeds the whole slice to the hasher in a single `write`. No length prefix is
+// added, so compose with an explicit length when hashing several slices.
+pub fn hash_slice<T: HashBytes, H: Hasher>(data: &[T], state: &mut H) {
+    state.write(as_bytes(data));
+}
+
+pub fn hash_pod<T: HashBytes>(data: &[T]) -> u64 {
+    let mut hasher = HashEngine::default();
+    hash_slice(data, &mut hasher);
+    hasher.finish()
+}
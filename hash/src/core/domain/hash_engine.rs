@@ -1,19 +1,25 @@
-#![allow(dead_code, unused_imports, dead_code)]
+#![allow(dead_code, unused_imports)]
 
-use std::{
+use core::{
     hash::{BuildHasher, Hasher},
     simd::u64x4,
 };
 
+use super::{
+    digest::{Digest64, Digest128},
+    xof::XofReader,
+};
 use crate::{
-    constants::{PRIME_DIFFUSE, PRIME_FINAL, PRIME_LANE, PRIME_MIX, PRIME_TAIL},
-    shared::helper::{load_stripe, load_u32, load_u64, round, round_simd},
+    constants::{DEFAULT_SEED, PRIME_DIFFUSE, PRIME_FINAL, PRIME_LANE, PRIME_MIX, PRIME_TAIL},
+    shared::helper::{avalanche, load_stripe, load_u32, load_u64, round, round_simd},
 };
 
-const BASE: u64 = 0xcafecafecafecafe;
+// Stripes consumed per iteration of the bulk loop in `write`.
+const STRIPES_PER_BLOCK: usize = 4;
 
 pub struct HashEngine {
     accumulators: u64x4,
+    secret: u64x4,
     buffer: [u8; 32],
     buffer_len: usize,
     bytes_done: usize,
@@ -21,25 +27,75 @@ pub struct HashEngine {
 
 impl HashEngine {
     pub fn new(seed: u64) -> Self {
-        let accumulators = u64x4::from_array([
-            seed.wrapping_add(PRIME_DIFFUSE).wrapping_add(PRIME_MIX),
-            seed.wrapping_add(PRIME_MIX),
-            seed,
-            seed.wrapping_add(PRIME_DIFFUSE),
-        ]);
-
         Self {
-            accumulators,
+            accumulators: initial_accumulators(seed),
+            secret: u64x4::splat(0),
             buffer: [0; 32],
             buffer_len: 0,
             bytes_done: 0,
         }
     }
+
+    // Keyed mode for hash-flooding resistance. The secret is XORed into every
+    // stripe before the multiply and into every tail word in `finish`, on top
+    // of perturbing the initial accumulators, so it cannot be cancelled out
+    // the way a bare seed can.
+    pub fn with_secret(secret: &[u8; 32]) -> Self {
+        let lanes = load_stripe(secret);
+
+        // Fold the whole secret through the mixing round so that every key bit
+        // reaches the seed nonlinearly; a plain XOR lets flips cancel out later.
+        let seed = lanes.to_array().into_iter().fold(0, round);
+
+        let mut engine = Self::new(seed);
+        engine.accumulators += lanes;
+        engine.secret = lanes;
+        engine
+    }
+
+    // Expands a 128-bit secret to the 256-bit form through the mixing round.
+    pub fn with_secret_128(secret: &[u8; 16]) -> Self {
+        let (keys, _) = secret.as_chunks::<8>();
+        let k0 = load_u64(&keys[0]);
+        let k1 = load_u64(&keys[1]);
+
+        let mut expanded = [0u8; 32];
+        expanded[0..8].copy_from_slice(&k0.to_le_bytes());
+        expanded[8..16].copy_from_slice(&k1.to_le_bytes());
+        expanded[16..24].copy_from_slice(&round(k0, PRIME_LANE).to_le_bytes());
+        expanded[24..32].copy_from_slice(&round(k1, PRIME_TAIL).to_le_bytes());
+
+        Self::with_secret(&expanded)
+    }
+
+    // Secret bytes starting at `pos` of the (zero-padded) tail, little-endian.
+    fn secret_word(&self, pos: usize) -> u64 {
+        self.secret[pos / 8 % 4] >> ((pos % 8) * 8)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct KeyedState {
+    secret: [u8; 32],
+}
+
+impl KeyedState {
+    pub fn new(secret: [u8; 32]) -> Self {
+        Self { secret }
+    }
+}
+
+impl BuildHasher for KeyedState {
+    type Hasher = HashEngine;
+
+    fn build_hasher(&self) -> HashEngine {
+        HashEngine::with_secret(&self.secret)
+    }
 }
 
 impl Default for HashEngine {
     fn default() -> Self {
-        Self::new(BASE)
+        Self::new(DEFAULT_SEED)
     }
 }
 
@@ -61,84 +117,203 @@ impl Hasher for HashEngine {
                 return;
             }
 
-            let input = load_stripe(&self.buffer);
+            let input = load_stripe(&self.buffer) ^ self.secret;
             self.accumulators = round_simd(self.accumulators, input);
             self.buffer_len = 0;
         }
 
-        // Process the remaining bytes in 32-byte chunks.
-        let remaining = &bytes[offset..];
-        let chunks = remaining.chunks_exact(32);
-        let remainder = chunks.remainder();
-
-        for chunk in chunks {
-            let input = load_stripe(chunk);
-            self.accumulators = round_simd(self.accumulators, input);
-        }
+        // Full stripes are loaded straight from the input; only the tail that
+        // cannot form a stripe is copied into the buffer.
+        let (stripes, remainder) = bytes[offset..].as_chunks::<32>();
+        self.accumulators = self.consume(stripes);
 
         self.buffer_len = remainder.len();
         self.buffer[..self.buffer_len].copy_from_slice(remainder);
     }
 
     fn finish(&self) -> u64 {
-        let mut state: u64;
-
-        // If the number of bytes processed is greater than or equal to 32,
-        // we can use the accumulators to compute the hash.
-        if self.bytes_done >= 32 {
-            state = self.accumulators[0]
-                .rotate_left(1)
-                .wrapping_add(self.accumulators[1].rotate_left(7))
-                .wrapping_add(self.accumulators[2].rotate_left(12))
-                .wrapping_add(self.accumulators[3].rotate_left(18));
-
-            for i in 0..4 {
-                state ^= round(0, self.accumulators[i]);
-                state = state.wrapping_mul(PRIME_DIFFUSE).wrapping_add(PRIME_FINAL);
+        avalanche(self.finish_state())
+    }
+}
+
+impl HashEngine {
+    pub fn finish_digest(&self) -> Digest64 {
+        Digest64::from(self.finish())
+    }
+
+    // The 64-bit digest followed by the next eight bytes of the XOF stream.
+    pub fn finish_digest128(&self) -> Digest128 {
+        let mut reader = self.finish_xof();
+        let hi = reader.next_u64() as u128;
+        let lo = reader.next_u64() as u128;
+        Digest128::from(hi << 64 | lo)
+    }
+
+    // Extendable output: the first 8 bytes of the stream are `finish()` in
+    // little-endian order, and any longer read extends a shorter one.
+    pub fn finish_xof(&self) -> XofReader {
+        let lanes = (self.accumulators ^ self.secret).to_array();
+        XofReader::new(self.finish_state(), lanes)
+    }
+
+    // Four stripes per iteration give the compiler a 128-byte body with no
+    // bounds checks; the rounds still run in input order, so the result is
+    // identical to consuming one stripe at a time.
+    fn consume(&self, stripes: &[[u8; 32]]) -> u64x4 {
+        let mut accumulators = self.accumulators;
+        let (blocks, rest) = stripes.as_chunks::<STRIPES_PER_BLOCK>();
+
+        for block in blocks {
+            for stripe in block {
+                accumulators = round_simd(accumulators, load_stripe(stripe) ^ self.secret);
             }
-        } else {
-            state = self.accumulators[2].wrapping_add(PRIME_TAIL);
         }
 
+        for stripe in rest {
+            accumulators = round_simd(accumulators, load_stripe(stripe) ^ self.secret);
+        }
+
+        accumulators
+    }
+
+    // Everything in `finish` up to the final avalanche.
+    fn finish_state(&self) -> u64 {
+        let mut state = if self.bytes_done >= 32 {
+            merge(self.accumulators, self.secret)
+        } else {
+            self.accumulators[2].wrapping_add(PRIME_TAIL)
+        };
+
         // Add the number of bytes processed to the state.
-        state = state.wrapping_add(self.bytes_done as u64);
+        state = state.wrapping_add(self.bytes_done as u64 ^ self.secret[3]);
 
-        // Process the remaining bytes in 32-byte chunks.
-        let buf = &self.buffer[..self.buffer_len];
+        // Fold in the bytes still buffered after the last full stripe.
+        let mut buf = &self.buffer[..self.buffer_len];
         let mut pos = 0;
 
-        while pos + 8 <= buf.len() {
-            state ^= round(0, load_u64(&buf[pos..pos + 8]));
-            state = state
-                .rotate_left(27)
-                .wrapping_mul(PRIME_DIFFUSE)
-                .wrapping_add(PRIME_FINAL);
+        while let Some((word, rest)) = buf.split_first_chunk::<8>() {
+            state = mix_u64(state, load_u64(word) ^ self.secret_word(pos));
+            buf = rest;
             pos += 8;
         }
 
-        while pos + 4 <= buf.len() {
-            let k = load_u32(&buf[pos..pos + 4]) as u64;
-            state ^= k.wrapping_mul(PRIME_DIFFUSE);
-            state = state
-                .rotate_left(23)
-                .wrapping_mul(PRIME_MIX)
-                .wrapping_add(PRIME_LANE);
+        while let Some((word, rest)) = buf.split_first_chunk::<4>() {
+            state = mix_u32(state, load_u32(word) ^ self.secret_word(pos) as u32);
+            buf = rest;
             pos += 4;
         }
 
-        while pos < buf.len() {
-            state ^= (buf[pos] as u64).wrapping_mul(PRIME_TAIL);
-            state = state.rotate_left(11).wrapping_mul(PRIME_DIFFUSE);
+        for &byte in buf {
+            state = mix_u8(state, byte ^ self.secret_word(pos) as u8);
             pos += 1;
         }
 
-        // Finalize the state.
-        state ^= state >> 33;
-        state = state.wrapping_mul(PRIME_MIX);
-        state ^= state >> 29;
-        state = state.wrapping_mul(PRIME_LANE);
-        state ^= state >> 32;
-
         state
     }
 }
+
+// One-shot hash of `bytes`, always equal to `HashEngine::new(seed)` fed the
+// same bytes and finished. Inputs of up to 32 bytes skip the engine, its
+// buffer copy and the tail loops: each length class below is a fixed sequence
+// of word reads that reproduces what `finish` would do for that length.
+pub fn hash_oneshot(bytes: &[u8], seed: u64) -> u64 {
+    let len = bytes.len();
+    let state = seed.wrapping_add(PRIME_TAIL).wrapping_add(len as u64);
+
+    let state = match len {
+        0..=3 => mix_bytes(state, bytes),
+        4..=8 => mix_up_to_8(state, bytes),
+        9..=16 => mix_up_to_16(state, bytes),
+        17..=31 => {
+            let (head, rest) = bytes.split_at(16);
+            mix_up_to_16(mix_up_to_16(state, head), rest)
+        }
+        // A single stripe and no tail, so `finish` reduces to the merge.
+        32 => {
+            let (stripes, _) = bytes.as_chunks::<32>();
+            let accumulators = stripes
+                .iter()
+                .fold(initial_accumulators(seed), |acc, stripe| {
+                    round_simd(acc, load_stripe(stripe))
+                });
+            merge(accumulators, u64x4::splat(0)).wrapping_add(32)
+        }
+        _ => {
+            let mut engine = HashEngine::new(seed);
+            engine.write(bytes);
+            return engine.finish();
+        }
+    };
+
+    avalanche(state)
+}
+
+fn initial_accumulators(seed: u64) -> u64x4 {
+    u64x4::from_array([
+        seed.wrapping_add(PRIME_DIFFUSE).wrapping_add(PRIME_MIX),
+        seed.wrapping_add(PRIME_MIX),
+        seed,
+        seed.wrapping_add(PRIME_DIFFUSE),
+    ])
+}
+
+// Collapses the four lanes once at least one full stripe has been consumed.
+fn merge(accumulators: u64x4, secret: u64x4) -> u64 {
+    let mut state = accumulators[0]
+        .rotate_left(1)
+        .wrapping_add(accumulators[1].rotate_left(7))
+        .wrapping_add(accumulators[2].rotate_left(12))
+        .wrapping_add(accumulators[3].rotate_left(18));
+
+    for (lane, key) in accumulators.to_array().into_iter().zip(secret.to_array()) {
+        state ^= round(0, lane ^ key);
+        state = state.wrapping_mul(PRIME_DIFFUSE).wrapping_add(PRIME_FINAL);
+    }
+
+    state
+}
+
+fn mix_u64(state: u64, word: u64) -> u64 {
+    (state ^ round(0, word))
+        .rotate_left(27)
+        .wrapping_mul(PRIME_DIFFUSE)
+        .wrapping_add(PRIME_FINAL)
+}
+
+fn mix_u32(state: u64, word: u32) -> u64 {
+    (state ^ (word as u64).wrapping_mul(PRIME_DIFFUSE))
+        .rotate_left(23)
+        .wrapping_mul(PRIME_MIX)
+        .wrapping_add(PRIME_LANE)
+}
+
+fn mix_u8(state: u64, byte: u8) -> u64 {
+    (state ^ (byte as u64).wrapping_mul(PRIME_TAIL))
+        .rotate_left(11)
+        .wrapping_mul(PRIME_DIFFUSE)
+}
+
+// The helpers below each take a tail no longer than their name says and
+// consume it the way `finish_state` does: whole words, then at most one
+// 4-byte half-word, then single bytes.
+fn mix_bytes(state: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(state, |state, &byte| mix_u8(state, byte))
+}
+
+fn mix_up_to_8(state: u64, bytes: &[u8]) -> u64 {
+    if let Some(word) = bytes.first_chunk::<8>() {
+        return mix_u64(state, load_u64(word));
+    }
+
+    match bytes.split_first_chunk::<4>() {
+        Some((word, rest)) => mix_bytes(mix_u32(state, load_u32(word)), rest),
+        None => mix_bytes(state, bytes),
+    }
+}
+
+fn mix_up_to_16(state: u64, bytes: &[u8]) -> u64 {
+    match bytes.split_first_chunk::<8>() {
+        Some((word, rest)) => mix_up_to_8(mix_u64(state, load_u64(word)), rest),
+        None => mix_up_to_8(state, bytes),
+    }
+}
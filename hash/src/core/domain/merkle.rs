@@ -0,0 +1,203 @@
+use alloc::vec::Vec;
+use core::hash::Hasher;
+
+use super::hash_engine::HashEngine;
+
+// Leaves and internal nodes are hashed under different prefixes so a leaf
+// can never be passed off as an internal node (second-preimage protection).
+const LEAF_TAG: u8 = 0x00;
+const NODE_TAG: u8 = 0x01;
+
+pub fn leaf_hash(data: &[u8]) -> u64 {
+    let mut hasher = HashEngine::default();
+    hasher.write(&[LEAF_TAG]);
+    hasher.write(data);
+    hasher.finish()
+}
+
+pub fn node_hash(left: u64, right: u64) -> u64 {
+    let mut hasher = HashEngine::default();
+    hasher.write(&[NODE_TAG]);
+    hasher.write(&left.to_le_bytes());
+    hasher.write(&right.to_le_bytes());
+    hasher.finish()
+}
+
+// Append-only binary Merkle tree. `levels[0]` holds the leaf hashes and each
+// level above pairs up the one below; a trailing node without a sibling is
+// promoted unchanged, which gives the same shape as RFC 6962.
+//
+// The node at `levels[h][i]` covers leaves `i << h` up to `(i + 1) << h`,
+// truncated at the leaf count.
+#[derive(Default, Clone)]
+pub struct MerkleTree {
+    levels: Vec<Vec<u64>>,
+}
+
+pub struct InclusionProof {
+    pub index: usize,
+    pub leaf_count: usize,
+    pub siblings: Vec<u64>,
+}
+
+impl MerkleTree {
+    pub fn new() -> Self {
+        Self { levels: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn append(&mut self, data: &[u8]) {
+        self.append_hash(leaf_hash(data));
+    }
+
+    pub fn append_hash(&mut self, leaf: u64) {
+        if self.levels.is_empty() {
+            self.levels.push(Vec::new());
+        }
+        self.levels[0].push(leaf);
+
+        // Only the rightmost node of each level can change on append.
+        let mut level = 0;
+        while self.levels[level].len() > 1 {
+            let nodes = &self.levels[level];
+            let last = nodes.len() - 1;
+            let parent = if last % 2 == 1 {
+                node_hash(nodes[last - 1], nodes[last])
+            } else {
+                nodes[last]
+            };
+
+            if self.levels.len() == level + 1 {
+                self.levels.push(Vec::new());
+            }
+
+            let above = &mut self.levels[level + 1];
+            let index = last / 2;
+            if index == above.len() {
+                above.push(parent);
+            } else {
+                above[index] = parent;
+            }
+
+            level += 1;
+        }
+    }
+
+    pub fn root(&self) -> Option<u64> {
+        self.levels.last().map(|top| top[0])
+    }
+
+    pub fn leaf(&self, index: usize) -> Option<u64> {
+        self.node(0, index)
+    }
+
+    pub fn proof(&self, index: usize) -> Option<InclusionProof> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let mut siblings = Vec::new();
+        let mut position = index;
+
+        for nodes in &self.levels[..self.levels.len() - 1] {
+            let sibling = position ^ 1;
+            if sibling < nodes.len() {
+                siblings.push(nodes[sibling]);
+            }
+            position /= 2;
+        }
+
+        Some(InclusionProof {
+            index,
+            leaf_count: self.len(),
+            siblings,
+        })
+    }
+
+    // Returns the indices of leaves that differ between the two trees,
+    // including leaves present in only one of them. Subtrees whose roots
+    // match and cover the same leaf range are skipped.
+    pub fn diff(&self, other: &MerkleTree) -> Vec<usize> {
+        let mut differing = Vec::new();
+        let height = self.levels.len().max(other.levels.len());
+
+        if height > 0 {
+            self.diff_node(other, height - 1, 0, &mut differing);
+        }
+
+        differing
+    }
+
+    fn diff_node(&self, other: &MerkleTree, level: usize, index: usize, out: &mut Vec<usize>) {
+        let ours = self.node(level, index);
+        let theirs = other.node(level, index);
+
+        if ours.is_none() && theirs.is_none() {
+            return;
+        }
+
+        if ours == theirs && self.span(level, index) == other.span(level, index) {
+            return;
+        }
+
+        if level == 0 {
+            out.push(index);
+            return;
+        }
+
+        self.diff_node(other, level - 1, index * 2, out);
+        self.diff_node(other, level - 1, index * 2 + 1, out);
+    }
+
+    fn node(&self, level: usize, index: usize) -> Option<u64> {
+        self.levels.get(level)?.get(index).copied()
+    }
+
+    fn span(&self, level: usize, index: usize) -> (usize, usize) {
+        let start = index << level;
+        let end = ((index + 1) << level).min(self.len());
+        (start, end)
+    }
+}
+
+impl InclusionProof {
+    pub fn verify(&self, data: &[u8], root: u64) -> bool {
+        self.verify_hash(leaf_hash(data), root)
+    }
+
+    pub fn verify_hash(&self, leaf: u64, root: u64) -> bool {
+        if self.index >= self.leaf_count {
+            return false;
+        }
+
+        let mut hash = leaf;
+        let mut position = self.index;
+        let mut count = self.leaf_count;
+        let mut siblings = self.siblings.iter();
+
+        while count > 1 {
+            let sibling = position ^ 1;
+            if sibling < count {
+                let Some(&other) = siblings.next() else {
+                    return false;
+                };
+                hash = if position.is_multiple_of(2) {
+                    node_hash(hash, other)
+                } else {
+                    node_hash(other, hash)
+                };
+            }
+            position /= 2;
+            count = count.div_ceil(2);
+        }
+
+        siblings.next().is_none() && hash == root
+    }
+}
@@ -0,0 +1,181 @@
+use core::hash::Hasher;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use super::hash_engine::HashEngine;
+
+pub use hash_derive::StableHash;
+
+// Structural hashing with a fixed byte encoding, so results can be persisted
+// and compared across Rust versions and 32/64-bit targets. Only
+// `Hasher::write` is ever called; the encoding is:
+//
+// - Integers: fixed-width little-endian. `usize`/`isize` are widened to 8 bytes.
+// - `bool`: one byte, 0 or 1. `char`: its scalar value as a 4-byte `u32`.
+// - `f32`/`f64`: the IEEE-754 bit pattern as `u32`/`u64`.
+// - `str`, slices, `Vec`: element count as a `u64`, then each element.
+// - Arrays `[T; N]`: each element, no count since `N` is part of the type.
+// - `Option`: byte 0 for `None`, byte 1 followed by the value for `Some`.
+// - Tuples and derived structs: fields in declaration order, no framing.
+// - Derived enums: variant index as a `u32`, then that variant's fields.
+// - `&T`, `&mut T` and `Box<T>`: encoded as `T`.
+pub trait StableHash {
+    fn stable_hash<H: Hasher>(&self, state: &mut H);
+}
+
+pub fn stable_hash<T: StableHash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = HashEngine::default();
+    value.stable_hash(&mut hasher);
+    hasher.finish()
+}
+
+pub fn stable_hash_seeded<T: StableHash + ?Sized>(value: &T, seed: u64) -> u64 {
+    let mut hasher = HashEngine::new(seed);
+    value.stable_hash(&mut hasher);
+    hasher.finish()
+}
+
+pub fn write_len<H: Hasher>(state: &mut H, len: usize) {
+    state.write(&(len as u64).to_le_bytes());
+}
+
+macro_rules! impl_le_bytes {
+    ($($ty:ty => $wire:ty),* $(,)?) => {
+        $(
+            impl StableHash for $ty {
+                fn stable_hash<H: Hasher>(&self, state: &mut H) {
+                    state.write(&(*self as $wire).to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_le_bytes! {
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    u128 => u128,
+    usize => u64,
+    i8 => i8,
+    i16 => i16,
+    i32 => i32,
+    i64 => i64,
+    i128 => i128,
+    isize => i64,
+    char => u32,
+}
+
+impl StableHash for bool {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        state.write(&[*self as u8]);
+    }
+}
+
+impl StableHash for f32 {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.to_bits().to_le_bytes());
+    }
+}
+
+impl StableHash for f64 {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.to_bits().to_le_bytes());
+    }
+}
+
+impl StableHash for () {
+    fn stable_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl StableHash for str {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        write_len(state, self.len());
+        state.write(self.as_bytes());
+    }
+}
+
+impl<T: StableHash> StableHash for [T] {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        write_len(state, self.len());
+        for item in self {
+            item.stable_hash(state);
+        }
+    }
+}
+
+impl<T: StableHash, const N: usize> StableHash for [T; N] {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        for item in self {
+            item.stable_hash(state);
+        }
+    }
+}
+
+impl<T: StableHash> StableHash for Option<T> {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            None => state.write(&[0]),
+            Some(value) => {
+                state.write(&[1]);
+                value.stable_hash(state);
+            }
+        }
+    }
+}
+
+impl<T: StableHash + ?Sized> StableHash for &T {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).stable_hash(state);
+    }
+}
+
+impl<T: StableHash + ?Sized> StableHash for &mut T {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).stable_hash(state);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl StableHash for String {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().stable_hash(state);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: StableHash> StableHash for Vec<T> {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().stable_hash(state);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: StableHash + ?Sized> StableHash for Box<T> {
+    fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).stable_hash(state);
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: StableHash),+> StableHash for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn stable_hash<H: Hasher>(&self, state: &mut H) {
+                let ($($name,)+) = self;
+                $($name.stable_hash(state);)+
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+impl_tuple!(A, B, C, D, E, F, G);
+impl_tuple!(A, B, C, D, E, F, G, I);
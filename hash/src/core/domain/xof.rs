@@ -0,0 +1,64 @@
+use crate::{
+    constants::{PRIME_FINAL, PRIME_LANE},
+    shared::helper::{avalanche, round},
+};
+
+// Squeezes an arbitrary-length byte stream out of a finalized `HashEngine`.
+// Block 0 is the regular 64-bit digest; block `n` re-mixes the pre-avalanche
+// state with accumulator lane `n % 4` and the block counter. The stream is
+// fixed by the input, so reading `a` bytes and then `b` bytes yields the same
+// bytes as a single read of `a + b`.
+//
+// Long inputs carry the full 256-bit accumulator state into the extra blocks.
+// Inputs under 32 bytes never reach the accumulators, so their extra output
+// is an expansion of the 64-bit state and adds no collision resistance.
+#[derive(Clone)]
+pub struct XofReader {
+    state: u64,
+    lanes: [u64; 4],
+    position: u64,
+}
+
+impl XofReader {
+    pub(crate) fn new(state: u64, lanes: [u64; 4]) -> Self {
+        Self {
+            state,
+            lanes,
+            position: 0,
+        }
+    }
+
+    pub fn read(&mut self, out: &mut [u8]) {
+        let mut filled = 0;
+
+        while filled < out.len() {
+            let offset = (self.position % 8) as usize;
+            let bytes = self.block(self.position / 8).to_le_bytes();
+            let take = (8 - offset).min(out.len() - filled);
+
+            out[filled..filled + take].copy_from_slice(&bytes[offset..offset + take]);
+            filled += take;
+            self.position += take as u64;
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.read(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    fn block(&self, index: u64) -> u64 {
+        if index == 0 {
+            return avalanche(self.state);
+        }
+
+        let lane = self.lanes[(index % 4) as usize];
+        let mixed = round(self.state ^ index.wrapping_mul(PRIME_FINAL), lane);
+        avalanche(mixed.wrapping_add(round(lane, index.wrapping_mul(PRIME_LANE))))
+    }
+}
@@ -1,11 +1,23 @@
+#![no_std]
 #![feature(portable_simd)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// Lets `#[derive(StableHash)]` expand to `::hash::...` paths inside this crate too.
+extern crate self as hash;
+
 pub mod constants;
 pub mod core;
 pub mod shared;
 
 use crate::core::domain::hash_engine::HashEngine;
-use std::hash::{Hash, Hasher};
+use ::core::hash::{Hash, Hasher};
+
+pub use crate::core::domain::hash_engine::hash_oneshot;
 
 pub fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
     let mut hasher = HashEngine::default();
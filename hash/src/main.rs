@@ -1,7 +1,97 @@
-use hash::hash;
+use std::{ffi::OsStr, path::Path, process::ExitCode};
 
-fn main() {
+use hash::{
+    core::domain::{
+        digest::Digest64,
+        fs_tree::{find_duplicates, hash_tree},
+    },
+    hash,
+};
+
+const GREETING: u64 = hash::hash!("Hello, world!");
+
+#[derive(Clone, Copy)]
+enum Encoding {
+    Hex,
+    Base32,
+    Base64,
+}
+
+impl Encoding {
+    fn parse(flag: &OsStr) -> Option<Self> {
+        match flag.to_str()? {
+            "--hex" => Some(Encoding::Hex),
+            "--base32" => Some(Encoding::Base32),
+            "--base64" => Some(Encoding::Base64),
+            _ => None,
+        }
+    }
+
+    fn format(self, digest: &Digest64) -> String {
+        match self {
+            Encoding::Hex => digest.to_string(),
+            Encoding::Base32 => digest.base32().to_string(),
+            Encoding::Base64 => digest.base64().to_string(),
+        }
+    }
+}
+
+fn demo() {
     let value = "Hello, world!";
     let hash_value = hash(value);
     println!("Hash of '{}': {}", value, hash_value);
+
+    let digest = Digest64::from(GREETING);
+    println!("Compile-time hash of '{}' bytes: {}", value, digest);
+    println!("  base32: {}", digest.base32());
+    println!("  base64: {}", digest.base64());
+}
+
+fn tree(path: &Path, encoding: Encoding) -> std::io::Result<()> {
+    println!("{}  {}", encoding.format(&hash_tree(path)?), path.display());
+    Ok(())
+}
+
+fn dupes(path: &Path) -> std::io::Result<()> {
+    for (i, set) in find_duplicates(path)?.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        for file in set {
+            println!("{}", file.display());
+        }
+    }
+    Ok(())
+}
+
+fn usage() -> ExitCode {
+    eprintln!("Usage: hash [tree [--hex|--base32|--base64] <dir> | dupes <dir>]");
+    ExitCode::FAILURE
+}
+
+fn main() -> ExitCode {
+    // Paths need not be UTF-8, so arguments stay `OsString`s.
+    let args: Vec<_> = std::env::args_os().skip(1).collect();
+
+    let result = match args.as_slice() {
+        [] => {
+            demo();
+            Ok(())
+        }
+        [command, path] if command == "tree" => tree(Path::new(path), Encoding::Hex),
+        [command, flag, path] if command == "tree" => match Encoding::parse(flag) {
+            Some(encoding) => tree(Path::new(path), encoding),
+            None => return usage(),
+        },
+        [command, path] if command == "dupes" => dupes(Path::new(path)),
+        _ => return usage(),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
 }
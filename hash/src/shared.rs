@@ -1 +1,4 @@
-pub mod helper;
\ No newline at end of file
+pub mod encoding;
+pub mod helper;
+#[cfg(feature = "std")]
+pub mod io;
@@ -0,0 +1,131 @@
+use core::fmt;
+
+// RFC 4648 alphabets. Encoders never emit padding; decoders accept and
+// ignore trailing `=` so padded input from other tools still parses.
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    InvalidLength,
+    InvalidCharacter,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidLength => f.write_str("invalid encoded length"),
+            DecodeError::InvalidCharacter => f.write_str("invalid character in encoded input"),
+        }
+    }
+}
+
+pub fn write_hex<W: fmt::Write>(out: &mut W, bytes: &[u8]) -> fmt::Result {
+    for &byte in bytes {
+        out.write_char(HEX[(byte >> 4) as usize] as char)?;
+        out.write_char(HEX[(byte & 0xf) as usize] as char)?;
+    }
+    Ok(())
+}
+
+pub fn decode_hex(input: &str, out: &mut [u8]) -> Result<(), DecodeError> {
+    let input = input.as_bytes();
+    if input.len() != out.len() * 2 {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    for (byte, pair) in out.iter_mut().zip(input.chunks_exact(2)) {
+        let hi = (pair[0] as char).to_digit(16);
+        let lo = (pair[1] as char).to_digit(16);
+        match (hi, lo) {
+            (Some(hi), Some(lo)) => *byte = (hi << 4 | lo) as u8,
+            _ => return Err(DecodeError::InvalidCharacter),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn write_base32<W: fmt::Write>(out: &mut W, bytes: &[u8]) -> fmt::Result {
+    write_radix(out, bytes, 5, BASE32)
+}
+
+pub fn decode_base32(input: &str, out: &mut [u8]) -> Result<(), DecodeError> {
+    decode_radix(input, out, 5, |c| {
+        let c = c.to_ascii_uppercase();
+        BASE32.iter().position(|&a| a == c)
+    })
+}
+
+pub fn write_base64<W: fmt::Write>(out: &mut W, bytes: &[u8]) -> fmt::Result {
+    write_radix(out, bytes, 6, BASE64)
+}
+
+pub fn decode_base64(input: &str, out: &mut [u8]) -> Result<(), DecodeError> {
+    decode_radix(input, out, 6, |c| BASE64.iter().position(|&a| a == c))
+}
+
+pub fn encoded_len(bytes: usize, bits: usize) -> usize {
+    (bytes * 8).div_ceil(bits)
+}
+
+fn write_radix<W: fmt::Write>(
+    out: &mut W,
+    bytes: &[u8],
+    bits: usize,
+    alphabet: &[u8],
+) -> fmt::Result {
+    let mask = (1u32 << bits) - 1;
+    let mut buffer = 0u32;
+    let mut pending = 0;
+
+    for &byte in bytes {
+        buffer = buffer << 8 | byte as u32;
+        pending += 8;
+        while pending >= bits {
+            pending -= bits;
+            out.write_char(alphabet[((buffer >> pending) & mask) as usize] as char)?;
+        }
+    }
+
+    if pending > 0 {
+        out.write_char(alphabet[((buffer << (bits - pending)) & mask) as usize] as char)?;
+    }
+
+    Ok(())
+}
+
+fn decode_radix(
+    input: &str,
+    out: &mut [u8],
+    bits: usize,
+    lookup: impl Fn(u8) -> Option<usize>,
+) -> Result<(), DecodeError> {
+    let input = input.trim_end_matches('=').as_bytes();
+    if input.len() != encoded_len(out.len(), bits) {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut buffer = 0u32;
+    let mut pending = 0;
+    let mut written = 0;
+
+    for &c in input {
+        let value = lookup(c).ok_or(DecodeError::InvalidCharacter)?;
+        buffer = buffer << bits | value as u32;
+        pending += bits;
+        if pending >= 8 {
+            pending -= 8;
+            out[written] = (buffer >> pending) as u8;
+            written += 1;
+        }
+    }
+
+    // Leftover bits must be the zero padding written by the encoder.
+    if buffer & ((1 << pending) - 1) != 0 {
+        return Err(DecodeError::InvalidCharacter);
+    }
+
+    Ok(())
+}
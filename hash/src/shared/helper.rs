@@ -1,19 +1,28 @@
-use std::simd::u64x4;
+use core::simd::{ToBytes, u8x32, u64x4};
 
-use crate::constants::{PRIME_DIFFUSE, PRIME_MIX};
+use crate::constants::{PRIME_DIFFUSE, PRIME_LANE, PRIME_MIX};
 
-pub fn round(accumulator: u64, input: u64) -> u64 {
+pub const fn round(accumulator: u64, input: u64) -> u64 {
     let mut acc = accumulator.wrapping_add(input.wrapping_mul(PRIME_MIX));
     acc = acc.rotate_left(31);
     acc.wrapping_mul(PRIME_DIFFUSE)
 }
 
-pub fn load_u64(bytes: &[u8]) -> u64 {
-    u64::from_le_bytes(bytes.try_into().unwrap())
+pub const fn avalanche(mut state: u64) -> u64 {
+    state ^= state >> 33;
+    state = state.wrapping_mul(PRIME_MIX);
+    state ^= state >> 29;
+    state = state.wrapping_mul(PRIME_LANE);
+    state ^= state >> 32;
+    state
 }
 
-pub fn load_u32(bytes: &[u8]) -> u32 {
-    u32::from_le_bytes(bytes.try_into().unwrap())
+pub fn load_u64(bytes: &[u8; 8]) -> u64 {
+    u64::from_le_bytes(*bytes)
+}
+
+pub fn load_u32(bytes: &[u8; 4]) -> u32 {
+    u32::from_le_bytes(*bytes)
 }
 
 pub fn rotate_left_simd(acc: u64x4, shift: u32) -> u64x4 {
@@ -28,11 +37,9 @@ pub fn round_simd(acc: u64x4, input: u64x4) -> u64x4 {
     acc * prime_diffuse
 }
 
-pub fn load_stripe(bytes: &[u8]) -> u64x4 {
-    u64x4::from_array([
-        load_u64(&bytes[0..8]),
-        load_u64(&bytes[8..16]),
-        load_u64(&bytes[16..24]),
-        load_u64(&bytes[24..32]),
-    ])
-}
\ No newline at end of file
+// One unaligned 32-byte load, reinterpreted as four little-endian lanes.
+// Taking a fixed-size array keeps the length check at the call site, where
+// `as_chunks` already proves it.
+pub fn load_stripe(bytes: &[u8; 32]) -> u64x4 {
+    u64x4::from_le_bytes(u8x32::from_array(*bytes))
+}
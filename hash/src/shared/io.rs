@@ -0,0 +1,28 @@
+use core::hash::Hasher;
+use std::io::{self, Read, Write};
+
+use crate::core::domain::{hash_engine::HashEngine, xof::XofReader};
+
+impl Write for HashEngine {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Hasher::write(self, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub fn hash_reader<R: Read>(reader: &mut R, seed: u64) -> io::Result<u64> {
+    let mut hasher = HashEngine::new(seed);
+    io::copy(reader, &mut hasher)?;
+    Ok(hasher.finish())
+}
+
+impl Read for XofReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        XofReader::read(self, buf);
+        Ok(buf.len())
+    }
+}
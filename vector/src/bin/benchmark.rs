@@ -0,0 +1,124 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use vector::core::domain::vector::MyVector;
+
+const WARMUP_ITERS: usize = 3;
+const SAMPLES: usize = 15;
+
+// Pushes per run and how many runs make one sample, so every sample moves
+// roughly the same number of elements.
+const SIZES: &[(usize, usize, &str)] = &[
+    (1_000, 10_000, "1 K"),
+    (100_000, 100, "100 K"),
+    (10_000_000, 1, "10 M"),
+];
+
+type BenchFn = fn(usize);
+
+const VECTORS: &[(&str, BenchFn)] = &[("Vec", push_vec), ("MyVector", push_my_vector)];
+
+struct Stats {
+    median: f64,
+    p5: f64,
+    p95: f64,
+}
+
+#[cfg(target_os = "linux")]
+fn pin_to_cpu(cpu: usize) -> bool {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(cpu, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_to_cpu(_cpu: usize) -> bool {
+    false
+}
+
+fn push_vec(count: usize) {
+    let mut v = Vec::new();
+    for i in 0..count {
+        v.push(black_box(i as u64));
+    }
+    black_box(v.as_ptr());
+}
+
+fn push_my_vector(count: usize) {
+    let mut v = MyVector::new();
+    for i in 0..count {
+        v.push(black_box(i as u64));
+    }
+    black_box(v.as_ptr());
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+// Nanoseconds per push.
+fn measure(count: usize, runs: usize, run: BenchFn) -> Stats {
+    for _ in 0..WARMUP_ITERS {
+        run(count);
+    }
+
+    let mut samples: Vec<f64> = (0..SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..runs {
+                run(count);
+            }
+            start.elapsed().as_nanos() as f64 / (count * runs) as f64
+        })
+        .collect();
+    samples.sort_by(f64::total_cmp);
+
+    Stats {
+        median: percentile(&samples, 50.0),
+        p5: percentile(&samples, 5.0),
+        p95: percentile(&samples, 95.0),
+    }
+}
+
+fn main() {
+    let cpu = std::env::var("BENCH_CPU")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    if pin_to_cpu(cpu) {
+        println!("Pinned to CPU {}", cpu);
+    }
+
+    println!();
+    println!(
+        "=== PUSH u64 from empty (ns/push, median of {} samples) ===",
+        SAMPLES
+    );
+    println!(
+        "{:<8} {:<10} {:>8} {:>8} {:>8} {:>10}",
+        "Pushes", "Vector", "Median", "P5", "P95", "Mpush/s"
+    );
+
+    for &(count, runs, label) in SIZES {
+        for &(name, run) in VECTORS {
+            let stats = measure(count, runs, run);
+            println!(
+                "{:<8} {:<10} {:>8.2} {:>8.2} {:>8.2} {:>10.0}",
+                label,
+                name,
+                stats.median,
+                stats.p5,
+                stats.p95,
+                1_000.0 / stats.median,
+            );
+        }
+    }
+
+    println!();
+}
@@ -0,0 +1,807 @@
+use std::{
+    alloc::Layout,
+    collections::{BTreeSet, HashSet},
+    hash::{BuildHasher, RandomState},
+    panic::{self, AssertUnwindSafe},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use vector::{
+    core::domain::{growth_policy::GrowthPolicy, vector::MyVector},
+    myvec,
+    shared::{
+        allocator::{AllocError, Allocator, Global, TryReserveError},
+        instrumented::{AllocStats, Instrumented},
+    },
+};
+
+const DROP_COUNT_LEN: usize = 1000;
+const PARITY_ROUNDS: usize = 5000;
+const TRAIT_ROUNDS: usize = 2000;
+const PANIC_LEN: usize = 100;
+
+// Every `Zst` and `Boxed` that is dropped bumps this counter, so each check can
+// compare the number of destructors run against what it expects.
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Zst;
+
+impl Drop for Zst {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone)]
+struct Boxed(#[allow(dead_code)] Box<u64>);
+
+impl Drop for Boxed {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// Panics in `drop` when armed, after being counted.
+struct Bomb(bool);
+
+impl Drop for Bomb {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+        if self.0 {
+            panic!("armed Bomb dropped");
+        }
+    }
+}
+
+// Panics in `clone` when armed; drops are counted.
+struct Fragile(bool);
+
+impl Clone for Fragile {
+    fn clone(&self) -> Self {
+        if self.0 {
+            panic!("armed Fragile cloned");
+        }
+        Fragile(false)
+    }
+}
+
+impl Drop for Fragile {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// Claims to be empty, whatever it yields.
+struct Understated(std::ops::Range<u64>);
+
+impl Iterator for Understated {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+}
+
+// Refuses every block larger than its limit in bytes, to drive the
+// fallible API's error paths.
+#[derive(Clone, Copy)]
+struct Limited(usize);
+
+unsafe impl Allocator for Limited {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        if layout.size() > self.0 {
+            return Err(AllocError);
+        }
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        if new_layout.size() > self.0 {
+            return Err(AllocError);
+        }
+        unsafe { Global.reallocate(ptr, old_layout, new_layout) }
+    }
+}
+
+fn drops() -> usize {
+    DROPS.swap(0, Ordering::Relaxed)
+}
+
+// Whether `f` panicked, without the panic message on stderr.
+fn panics(f: impl FnOnce()) -> bool {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+    result.is_err()
+}
+
+// xorshift64, so every run replays the same operations.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // Uniform enough in `0..n`; `n` must be nonzero.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn value(&mut self) -> u32 {
+        self.below(16) as u32
+    }
+}
+
+#[derive(Default)]
+struct Checks {
+    checked: usize,
+    failures: usize,
+}
+
+impl Checks {
+    fn check(&mut self, ok: bool) {
+        self.checked += 1;
+        if !ok {
+            self.failures += 1;
+        }
+    }
+
+    fn report(self) {
+        println!("  Checked:    {}", self.checked);
+        println!("  Failures:   {}", self.failures);
+
+        let result = if self.failures == 0 { "PASS" } else { "FAIL" };
+        println!("  Result:     {}", result);
+        println!();
+    }
+}
+
+fn main() {
+    println!();
+    test_zst();
+    test_drop_counts();
+    test_instrumented();
+    test_vec_parity();
+    test_traits();
+    test_panic_safety();
+    test_fallible();
+}
+
+fn test_zst() {
+    println!("=== ZST TEST ===");
+
+    let mut checks = Checks::default();
+    let instrumented = Instrumented::new();
+
+    let mut v = MyVector::<Zst, _>::with_capacity_in(100, &instrumented);
+    checks.check(v.capacity() == usize::MAX);
+
+    for _ in 0..DROP_COUNT_LEN {
+        v.push(Zst);
+    }
+    v.insert(0, Zst);
+    drop(v.remove(0));
+    v.reserve(usize::MAX - v.len());
+    v.reserve_exact(1);
+    v.shrink_to_fit();
+    checks.check(v.len() == DROP_COUNT_LEN && v.capacity() == usize::MAX);
+
+    let overflow = v.try_reserve(usize::MAX);
+    checks.check(overflow == Err(TryReserveError::CapacityOverflow));
+    checks.check(v.len() == DROP_COUNT_LEN);
+    drop(v);
+
+    // Nothing above may reach the allocator, not even to free.
+    checks.check(instrumented.stats() == AllocStats::default());
+    checks.check(drops() == DROP_COUNT_LEN + 1);
+
+    let mut units = MyVector::new();
+    for _ in 0..10 {
+        units.push(());
+    }
+    checks.check((&units).into_iter().count() == 10);
+    checks.check(units.into_iter().rev().count() == 10);
+
+    checks.report();
+}
+
+fn test_drop_counts() {
+    println!("=== DROP COUNT TEST ===");
+
+    let mut checks = Checks::default();
+    drops();
+
+    check_drop_counts(&mut checks, || Zst);
+    check_drop_counts(&mut checks, || Boxed(Box::new(7)));
+
+    checks.report();
+}
+
+// Runs the same sequence for a zero-sized and a heap-owning element type:
+// every value must be dropped exactly once, whichever way it leaves.
+fn check_drop_counts<T>(checks: &mut Checks, make: impl Fn() -> T) {
+    let filled = || {
+        let mut v = MyVector::new();
+        for _ in 0..DROP_COUNT_LEN {
+            v.push(make());
+        }
+        v
+    };
+
+    let mut v = filled();
+    for _ in 0..10 {
+        drop(v.pop());
+    }
+    checks.check(drops() == 10);
+
+    drop(v.remove(0));
+    checks.check(drops() == 1);
+
+    v.clear();
+    checks.check(drops() == DROP_COUNT_LEN - 11 && v.is_empty());
+
+    v.push(make());
+    drop(v);
+    checks.check(drops() == 1);
+
+    drop(filled());
+    checks.check(drops() == DROP_COUNT_LEN);
+
+    let mut iter = filled().into_iter();
+    let taken = iter.by_ref().take(3).count() + iter.by_ref().rev().take(2).count();
+    checks.check(taken == 5 && iter.len() == DROP_COUNT_LEN - 5);
+    checks.check(drops() == 5);
+
+    drop(iter);
+    checks.check(drops() == DROP_COUNT_LEN - 5);
+
+    let mut v = filled();
+    v.truncate(DROP_COUNT_LEN - 100);
+    checks.check(drops() == 100);
+
+    drop(v.swap_remove(0));
+    let taken = v.drain(100..200).take(10).count();
+    checks.check(taken == 10 && drops() == 101);
+
+    let mut i = 0usize;
+    v.retain(|_| {
+        i += 1;
+        i.is_multiple_of(2)
+    });
+    checks.check(drops() == 400 && v.len() == 399);
+
+    let mut tail = v.split_off(99);
+    v.append(&mut tail);
+    checks.check(drops() == 0 && v.len() == 399 && tail.is_empty());
+
+    v.dedup_by_key(|_| ());
+    checks.check(drops() == 398 && v.len() == 1);
+
+    drop(v);
+    checks.check(drops() == 1);
+}
+
+fn test_instrumented() {
+    println!("=== INSTRUMENTED ALLOCATOR TEST ===");
+
+    let mut checks = Checks::default();
+    let instrumented = Instrumented::new();
+
+    let mut v = MyVector::new_in(&instrumented);
+    let mut grows = 0;
+    for i in 0..DROP_COUNT_LEN as u64 {
+        let capacity = v.capacity();
+        v.push(i);
+        grows += (v.capacity() != capacity) as usize;
+    }
+
+    // The first growth allocates, every later one reallocates.
+    let stats = instrumented.stats();
+    let bytes = v.capacity() * size_of::<u64>();
+    checks.check(stats.allocations == 1 && stats.reallocations == grows - 1);
+    checks.check(stats.frees == 0 && stats.live_bytes() == bytes);
+    checks.check(stats.peak_bytes == bytes);
+
+    v.shrink_to_fit();
+    checks.check(instrumented.stats().reallocations == grows);
+    checks.check(instrumented.stats().live_bytes() == DROP_COUNT_LEN * size_of::<u64>());
+
+    drop(v);
+    let stats = instrumented.stats();
+    checks.check(stats.frees == 1 && stats.live_bytes() == 0 && stats.peak_bytes == bytes);
+
+    let overflow = MyVector::<u64, _>::try_with_capacity_in(usize::MAX, &instrumented);
+    checks.check(overflow.is_err() && instrumented.stats().failures == 0);
+
+    instrumented.reset();
+    checks.check(instrumented.stats() == AllocStats::default());
+
+    // A block that outlives a reset stays live, and freeing it afterwards
+    // leaves nothing live rather than underflowing.
+    let mut v = MyVector::new_in(&instrumented);
+    v.push(1u64);
+    let bytes = v.capacity() * size_of::<u64>();
+    instrumented.reset();
+    let stats = instrumented.stats();
+    checks.check(stats.allocations == 0 && stats.live_bytes() == bytes);
+    checks.check(stats.peak_bytes == bytes);
+
+    drop(v);
+    let stats = instrumented.stats();
+    checks.check(stats.frees == 1 && stats.bytes_freed == bytes && stats.live_bytes() == 0);
+    checks.check(stats.to_string().contains(" live=0B "));
+
+    checks.report();
+}
+
+fn test_vec_parity() {
+    println!("=== VEC PARITY TEST ===");
+
+    let mut checks = Checks::default();
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    let instrumented = Instrumented::new();
+    let mut mine = MyVector::new_in(&instrumented);
+    let mut std = Vec::new();
+
+    // Each round applies one random operation to both vectors, then
+    // compares whatever it returned and the full contents.
+    for _ in 0..PARITY_ROUNDS {
+        let len = std.len();
+
+        match rng.below(13) {
+            0 => {
+                let (a, b) = (rng.below(len + 1), rng.below(len + 1));
+                let (start, end) = (a.min(b), a.max(b));
+                let take = rng.below(end - start + 1);
+
+                let got: Vec<u32> = mine.drain(start..end).take(take).collect();
+                let want: Vec<u32> = std.drain(start..end).take(take).collect();
+                checks.check(got == want);
+            }
+            1 => {
+                let m = rng.below(4) as u32 + 2;
+                mine.retain(|x| !x.is_multiple_of(m));
+                std.retain(|x| !x.is_multiple_of(m));
+            }
+            2 => {
+                let bump = |x: &mut u32| {
+                    *x = (*x + 1) % 16;
+                    !x.is_multiple_of(3)
+                };
+                mine.retain_mut(bump);
+                std.retain_mut(bump);
+            }
+            3 => {
+                let d = rng.below(4) as u32 + 1;
+                mine.dedup_by_key(|x| *x / d);
+                std.dedup_by_key(|x| *x / d);
+            }
+            4 => {
+                let n = rng.below(len + 4);
+                mine.truncate(n);
+                std.truncate(n);
+            }
+            5 => {
+                let (n, value) = (rng.below(len + 8), rng.value());
+                mine.resize(n, value);
+                std.resize(n, value);
+            }
+            6 => {
+                let n = rng.below(len + 8);
+                let counter = |mut next: u32| {
+                    move || {
+                        next = (next + 1) % 16;
+                        next
+                    }
+                };
+                mine.resize_with(n, counter(0));
+                std.resize_with(n, counter(0));
+            }
+            7 => {
+                let at = rng.below(len + 1);
+                let mut mine_tail = mine.split_off(at);
+                let mut std_tail = std.split_off(at);
+                checks.check(mine_tail[..] == std_tail[..]);
+
+                // Put it back so the vectors keep some length.
+                mine.append(&mut mine_tail);
+                std.append(&mut std_tail);
+                checks.check(mine_tail.is_empty() && std_tail.is_empty());
+            }
+            8 if len > 0 => {
+                let index = rng.below(len);
+                checks.check(mine.swap_remove(index) == std.swap_remove(index));
+            }
+            9 => {
+                let items: Vec<u32> = (0..rng.below(8)).map(|_| rng.value()).collect();
+                mine.extend_from_slice(&items);
+                std.extend_from_slice(&items);
+            }
+            10 => {
+                let (a, b) = (rng.below(len + 1), rng.below(len + 1));
+                let (start, end) = (a.min(b), a.max(b));
+                let items: Vec<u32> = (0..rng.below(24)).map(|_| rng.value()).collect();
+                let take = rng.below(end - start + 1);
+                let before = instrumented.stats();
+
+                // A `filter` has no exact size hint, so both refill paths run.
+                // Items beyond the range then go through a temporary buffer.
+                let (got, want, leftover): (Vec<u32>, Vec<u32>, usize) = if rng.below(2) == 0 {
+                    (
+                        mine.splice(start..end, items.clone()).take(take).collect(),
+                        std.splice(start..end, items).take(take).collect(),
+                        0,
+                    )
+                } else {
+                    let odd = items.iter().copied().filter(|x| x % 2 == 1);
+                    let leftover = odd.clone().count().saturating_sub(end - start);
+                    (
+                        mine.splice(start..end, odd.clone()).take(take).collect(),
+                        std.splice(start..end, odd).take(take).collect(),
+                        leftover,
+                    )
+                };
+                checks.check(got == want);
+
+                // The temporary buffer grows by doubling from empty: one
+                // allocation, a reallocation per doubling, and one free. The
+                // vector's own buffer accounts for at most one more event.
+                let temporary = match leftover {
+                    0 => 0,
+                    n => 1 + (usize::BITS - (n - 1).leading_zeros()) as usize,
+                };
+                let after = instrumented.stats();
+                let events = |stats: AllocStats| stats.allocations + stats.reallocations;
+                checks.check(events(after) - events(before) <= temporary + 1);
+                checks.check(after.frees - before.frees == (leftover > 0) as usize);
+                checks.check(after.live_bytes() == mine.capacity() * size_of::<u32>());
+            }
+            11 => {
+                let (m, take) = (rng.below(4) as u32 + 2, rng.below(len + 1));
+                let got: Vec<u32> = mine
+                    .extract_if(|x| x.is_multiple_of(m))
+                    .take(take)
+                    .collect();
+                let want: Vec<u32> = std
+                    .extract_if(.., |x| x.is_multiple_of(m))
+                    .take(take)
+                    .collect();
+                checks.check(got == want);
+            }
+            _ => {
+                for _ in 0..rng.below(8) {
+                    let value = rng.value();
+                    mine.push(value);
+                    std.push(value);
+                }
+            }
+        }
+
+        checks.check(mine[..] == std[..]);
+    }
+
+    checks.report();
+}
+
+fn test_traits() {
+    println!("=== STANDARD TRAITS TEST ===");
+
+    let mut checks = Checks::default();
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    let state = RandomState::new();
+
+    // Short vectors over a tiny alphabet, so equal pairs, shared prefixes
+    // and every ordering come up often.
+    for _ in 0..TRAIT_ROUNDS {
+        let a: Vec<u32> = (0..rng.below(4)).map(|_| rng.below(3) as u32).collect();
+        let b: Vec<u32> = (0..rng.below(4)).map(|_| rng.below(3) as u32).collect();
+        let (mine_a, mine_b) = (MyVector::from(a.clone()), MyVector::from(&b[..]));
+
+        checks.check((mine_a == mine_b) == (a == b) && mine_a == a && mine_b == b[..]);
+        checks.check(mine_a.cmp(&mine_b) == a.cmp(&b));
+        checks.check(mine_a.partial_cmp(&mine_b) == a.partial_cmp(&b));
+        checks.check(state.hash_one(&mine_a) == state.hash_one(&a));
+        checks.check(format!("{:?}", mine_a) == format!("{:?}", a));
+    }
+
+    // `clone` copies the policy and allocator; `clone_from` reuses the buffer.
+    let instrumented = Instrumented::new();
+    let mut original = MyVector::new_in(&instrumented);
+    original.set_growth_policy(GrowthPolicy::Exact);
+    original.extend(0..100u64);
+
+    let mut copy = original.clone();
+    checks.check(copy == original && copy.capacity() == 100);
+    checks.check(copy.growth_policy() == GrowthPolicy::Exact);
+    checks.check(instrumented.stats().allocations == 2);
+
+    copy[0] = 7;
+    checks.check(original[0] == 0);
+
+    copy.truncate(50);
+    let before = instrumented.stats();
+    copy.clone_from(&original);
+    checks.check(copy == original && instrumented.stats() == before);
+
+    checks.check(MyVector::<u64>::default().is_empty());
+
+    let squares: MyVector<u64> = (0..10).map(|x| x * x).collect();
+    let expected: Vec<u64> = (0..10).map(|x| x * x).collect();
+    checks.check(squares == expected);
+
+    let mut copied: MyVector<u64> = MyVector::new();
+    copied.extend(&[1, 2, 3]);
+    copied.extend(expected.iter());
+    checks.check(copied[..3] == [1, 2, 3] && copied[3..] == expected[..]);
+
+    // Arrays move in without cloning; a `Vec` hands over its buffer.
+    drops();
+    drop(MyVector::from([Boxed(Box::new(1)), Boxed(Box::new(2))]));
+    checks.check(drops() == 2);
+
+    let vec = vec![5u64; 100];
+    let (ptr, capacity) = (vec.as_ptr(), vec.capacity());
+    let mut adopted = MyVector::from(vec);
+    checks.check(adopted.as_ptr() == ptr && adopted.capacity() == capacity);
+    adopted.push(6);
+    checks.check(adopted.len() == 101 && adopted[100] == 6);
+
+    let units = MyVector::from(vec![(); 5]);
+    checks.check(units.len() == 5 && units.capacity() == usize::MAX);
+
+    // `Borrow<[T]>` lets sets of vectors be searched with plain slices.
+    let words = ["alpha", "beta", "gamma"].map(|w| MyVector::from(w.as_bytes()));
+    let hashed: HashSet<MyVector<u8>> = words.iter().cloned().collect();
+    let ordered: BTreeSet<MyVector<u8>> = words.into_iter().collect();
+    checks.check(hashed.contains(&b"beta"[..]) && !hashed.contains(&b"delta"[..]));
+    checks.check(ordered.contains(&b"gamma"[..]) && !ordered.contains(&b"bet"[..]));
+
+    fn total(items: impl AsRef<[u32]>) -> u32 {
+        items.as_ref().iter().sum()
+    }
+    checks.check(total(myvec![1, 2, 3]) == 6);
+
+    // `myvec!` takes the same forms as `vec!`.
+    let empty: MyVector<u32> = myvec![];
+    checks.check(empty.is_empty() && myvec![1, 2, 3,] == [1, 2, 3]);
+    checks.check(myvec![String::from("a"); 3] == vec![String::from("a"); 3]);
+
+    let filled = myvec![Boxed(Box::new(9)); 4];
+    checks.check(filled.len() == 4 && drops() == 0);
+    drop(filled);
+    checks.check(drops() == 4);
+
+    drop(myvec![Boxed(Box::new(9)); 0]);
+    checks.check(drops() == 1);
+
+    checks.report();
+}
+
+// `PANIC_LEN` bombs, only the one at `armed` going off.
+fn armed_bombs<A: Allocator>(armed: usize, allocator: A) -> MyVector<Bomb, A> {
+    let mut v = MyVector::new_in(allocator);
+    v.extend((0..PANIC_LEN).map(|i| Bomb(i == armed)));
+    v
+}
+
+fn test_panic_safety() {
+    println!("=== PANIC SAFETY TEST ===");
+
+    let mut checks = Checks::default();
+    let instrumented = Instrumented::new();
+    drops();
+
+    // A panicking destructor does not stop the others, and the buffer is
+    // still freed.
+    let v = armed_bombs(PANIC_LEN / 2, &instrumented);
+    checks.check(panics(|| drop(v)));
+    checks.check(drops() == PANIC_LEN && instrumented.stats().live_bytes() == 0);
+
+    let mut v = armed_bombs(3, &instrumented);
+    checks.check(panics(|| v.clear()));
+    checks.check(drops() == PANIC_LEN && v.is_empty());
+    drop(v);
+    checks.check(drops() == 0);
+
+    let mut v = armed_bombs(PANIC_LEN - 1, &instrumented);
+    checks.check(panics(|| v.truncate(PANIC_LEN / 2)));
+    checks.check(drops() == PANIC_LEN / 2 && v.len() == PANIC_LEN / 2);
+    drop(v);
+    checks.check(drops() == PANIC_LEN / 2);
+
+    let mut iter = armed_bombs(PANIC_LEN / 2, &instrumented).into_iter();
+    iter.by_ref().take(2).for_each(drop);
+    checks.check(drops() == 2);
+    checks.check(panics(|| drop(iter)));
+    checks.check(drops() == PANIC_LEN - 2);
+    checks.check(instrumented.stats().live_bytes() == 0);
+
+    // A panicking iterator or closure keeps what was appended before it.
+    let boxed = |i: usize| Boxed(Box::new(i as u64));
+    let mut v = MyVector::new_in(&instrumented);
+    let items = (0..PANIC_LEN).map(|i| if i == 37 { panic!() } else { boxed(i) });
+    checks.check(panics(|| v.extend(items)));
+    checks.check(v.len() == 37 && drops() == 0);
+
+    // Without a size hint the iterator panics across several growths.
+    let items = (0..PANIC_LEN).filter(|&i| i != 73 || panic!()).map(boxed);
+    checks.check(panics(|| v.extend(items)));
+    checks.check(v.len() == 37 + 73 && drops() == 0);
+
+    let mut made = 0;
+    let make = || {
+        made += 1;
+        if made == 6 { panic!() } else { boxed(made) }
+    };
+    checks.check(panics(|| v.resize_with(v.len() + 10, make)));
+    checks.check(v.len() == 37 + 73 + 5 && drops() == 0);
+    drop(v);
+    checks.check(drops() == 37 + 73 + 5);
+
+    // A size hint that understates is only a hint.
+    let mut v = MyVector::new();
+    v.extend(Understated(0..PANIC_LEN as u64));
+    checks.check(v[..] == (0..PANIC_LEN as u64).collect::<Vec<_>>()[..]);
+
+    // A panicking `clone` keeps the clones made so far, or drops them with
+    // the unfinished copy.
+    let source = MyVector::from([
+        Fragile(false),
+        Fragile(false),
+        Fragile(true),
+        Fragile(false),
+    ]);
+    let mut v = MyVector::new();
+    checks.check(panics(|| v.extend_from_slice(&source)));
+    checks.check(v.len() == 2 && drops() == 0);
+    checks.check(panics(|| drop(source.clone())));
+    checks.check(drops() == 2);
+    drop((v, source));
+    checks.check(drops() == 6);
+
+    // A panicking predicate or key keeps the unvisited elements, in order.
+    let mut numbers: MyVector<u32> = (0..10).collect();
+    let keep = |x: &u32| if *x == 6 { panic!() } else { x % 2 == 1 };
+    checks.check(panics(|| numbers.retain(keep)));
+    checks.check(numbers[..] == [1, 3, 5, 6, 7, 8, 9]);
+
+    let mut numbers = myvec![1u32, 1, 2, 2, 3, 3];
+    let key = |x: &mut u32| if *x == 3 { panic!() } else { *x };
+    checks.check(panics(|| numbers.dedup_by_key(key)));
+    checks.check(numbers[..] == [1, 2, 3, 3]);
+
+    // So does a panicking destructor of a removed element.
+    let mut v = armed_bombs(4, &instrumented);
+    checks.check(panics(|| v.retain(|bomb| !bomb.0)));
+    checks.check(drops() == 1 && v.len() == PANIC_LEN - 1);
+    drop(v);
+    checks.check(drops() == PANIC_LEN - 1);
+
+    // Dropping a `Drain` whose undrained elements panic still drops the rest
+    // once and moves the tail back.
+    let mut bombs = MyVector::new();
+    for armed in [false, true, false, false, false] {
+        bombs.push(Bomb(armed));
+    }
+    checks.check(panics(|| drop(bombs.drain(1..3))));
+    checks.check(drops() == 2 && bombs.len() == 3);
+
+    // A leaked `Drain` leaks the range and the tail, never exposes them.
+    std::mem::forget(bombs.drain(1..2));
+    checks.check(bombs.len() == 1);
+    drop(bombs);
+    checks.check(drops() == 1);
+
+    // A replacement iterator that panics keeps the items it made, in place.
+    let mut bombs: MyVector<Bomb> = MyVector::new();
+    bombs.resize_with(6, || Bomb(false));
+    let replace_with = (0..).map(|i| if i < 2 { Bomb(false) } else { panic!() });
+    checks.check(panics(|| drop(bombs.splice(1..3, replace_with))));
+    checks.check(drops() == 2 && bombs.len() == 6);
+    drop(bombs);
+    checks.check(drops() == 6);
+
+    // So does a removed element whose destructor panics; the replacement is
+    // then dropped unused.
+    let mut bombs = MyVector::new();
+    for armed in [false, true, false, false] {
+        bombs.push(Bomb(armed));
+    }
+    let replace_with = [Bomb(false), Bomb(false), Bomb(false)];
+    checks.check(panics(|| drop(bombs.splice(1..3, replace_with))));
+    checks.check(drops() == 5 && bombs.len() == 2);
+    drop(bombs);
+    checks.check(drops() == 2);
+
+    // A panicking predicate keeps the current and all unvisited elements.
+    let mut numbers: MyVector<u32> = MyVector::new();
+    numbers.extend(0..10);
+    let predicate = |x: &mut u32| {
+        if *x == 6 {
+            panic!()
+        } else {
+            x.is_multiple_of(2)
+        }
+    };
+    checks.check(panics(|| numbers.extract_if(predicate).for_each(drop)));
+    checks.check(numbers[..] == [1, 3, 5, 6, 7, 8, 9]);
+
+    checks.report();
+}
+
+fn test_fallible() {
+    println!("=== FALLIBLE API TEST ===");
+
+    let mut checks = Checks::default();
+    let limited = Limited(size_of::<[u64; 8]>());
+    let refused = |result: Result<(), TryReserveError>| {
+        matches!(result, Err(TryReserveError::AllocError { .. }))
+    };
+
+    // Every growing operation fails without touching the vector once the
+    // allocator refuses more than eight `u64`s.
+    let mut v = MyVector::with_capacity_in(4, limited);
+    v.extend(0..4u64);
+
+    checks.check(refused(v.try_resize(9, 7)));
+    let mut calls = 0;
+    checks.check(refused(v.try_resize_with(9, || {
+        calls += 1;
+        calls
+    })));
+    checks.check(calls == 0);
+    checks.check(refused(v.try_extend_from_slice(&[7; 5])));
+
+    let mut other = MyVector::new_in(limited);
+    other.extend(4..9u64);
+    checks.check(refused(v.try_append(&mut other)));
+    checks.check(v[..] == [0, 1, 2, 3] && v.capacity() == 4 && other.len() == 5);
+
+    // Within the limit they behave like the infallible forms.
+    other.truncate(2);
+    checks.check(v.try_append(&mut other).is_ok() && other.is_empty());
+    checks.check(v.try_extend_from_slice(&[6]).is_ok());
+    checks.check(v.try_resize(8, 9).is_ok());
+    checks.check(v[..] == [0, 1, 2, 3, 4, 5, 6, 9]);
+
+    let tail = v.try_split_off(6);
+    checks.check(tail.is_ok_and(|tail| tail[..] == [6, 9]) && v.len() == 6);
+
+    let overflow = v.try_resize_with(usize::MAX, || 0);
+    checks.check(overflow == Err(TryReserveError::CapacityOverflow) && v.len() == 6);
+
+    // A refused push or insert hands the item back.
+    v.extend([6, 9]);
+    let pushed = v.try_push(10);
+    checks.check(matches!(
+        pushed,
+        Err((10, TryReserveError::AllocError { .. }))
+    ));
+    let inserted = v.try_insert(0, 11);
+    checks.check(matches!(
+        inserted,
+        Err((11, TryReserveError::AllocError { .. }))
+    ));
+    checks.check(v[..] == [0, 1, 2, 3, 4, 5, 6, 9] && v.capacity() == 8);
+
+    checks.report();
+}
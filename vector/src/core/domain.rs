@@ -1,2 +1,6 @@
+pub mod drain;
+pub mod extract_if;
+pub mod growth_policy;
 pub mod into_iter;
+pub mod splice;
 pub mod vector;
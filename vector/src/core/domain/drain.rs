@@ -0,0 +1,178 @@
+#![allow(dead_code, unused_imports)]
+
+use core::{iter::FusedIterator, marker::PhantomData, ptr, ptr::NonNull};
+
+use super::vector::MyVector;
+use crate::shared::allocator::{Allocator, Global};
+
+// Removes a range from a `MyVector`. While it is alive the vector's length
+// stops at the start of the range, so leaking the iterator only leaks the
+// drained elements and the tail. Elements in `head..end` are still to be
+// yielded; the kept tail is `tail_start..tail_start + tail_len`.
+pub struct Drain<'a, T, A: Allocator = Global> {
+    vec: NonNull<MyVector<T, A>>,
+    head: usize,
+    end: usize,
+    tail_start: usize,
+    tail_len: usize,
+    marker: PhantomData<&'a mut MyVector<T, A>>,
+}
+
+impl<'a, T, A: Allocator> Drain<'a, T, A> {
+    // `vec.len()` must already be `start`, with `start..end` and the tail
+    // `end..len` still initialized behind it.
+    pub(crate) fn new(vec: &'a mut MyVector<T, A>, start: usize, end: usize, len: usize) -> Self {
+        Self {
+            vec: NonNull::from(vec),
+            head: start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn allocator(&self) -> &A {
+        unsafe { self.vec.as_ref().allocator() }
+    }
+
+    pub(crate) fn allocator_ptr(&self) -> *const A {
+        MyVector::allocator_ptr(self.vec.as_ptr())
+    }
+
+    // The elements not yet yielded.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            let buf = self.vec.as_ref().as_ptr();
+            core::slice::from_raw_parts(buf.add(self.head), self.end - self.head)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.head
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head == self.end
+    }
+
+    fn buf(&mut self) -> *mut T {
+        unsafe { self.vec.as_mut().as_mut_ptr() }
+    }
+
+    // Writes items from `replace_with` into the gap between the vector's
+    // length and the tail, bumping the length after every write. Returns
+    // whether the gap was filled, i.e. whether more items may be wanted.
+    pub(crate) fn fill(&mut self, replace_with: &mut impl Iterator<Item = T>) -> bool {
+        let vec = unsafe { self.vec.as_mut() };
+
+        while vec.len() != self.tail_start {
+            let Some(item) = replace_with.next() else {
+                return false;
+            };
+
+            unsafe {
+                ptr::write(vec.as_mut_ptr().add(vec.len()), item);
+                vec.set_len(vec.len() + 1);
+            }
+        }
+
+        true
+    }
+
+    // Makes the gap `additional` slots wider by moving the tail right,
+    // growing the buffer first if needed. The vector's length must be at
+    // `tail_start`, so the buffer carries the tail along if it moves.
+    pub(crate) fn move_tail(&mut self, additional: usize) {
+        let vec = unsafe { self.vec.as_mut() };
+        vec.reserve(self.tail_len + additional);
+
+        let new_tail_start = self.tail_start + additional;
+        unsafe {
+            let buf = vec.as_mut_ptr();
+            ptr::copy(
+                buf.add(self.tail_start),
+                buf.add(new_tail_start),
+                self.tail_len,
+            );
+        }
+        self.tail_start = new_tail_start;
+    }
+}
+
+impl<T, A: Allocator> Iterator for Drain<'_, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.head == self.end {
+            return None;
+        }
+
+        let item = unsafe { ptr::read(self.buf().add(self.head)) };
+        self.head += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for Drain<'_, T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.head == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(unsafe { ptr::read(self.buf().add(self.end)) })
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for Drain<'_, T, A> {
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+}
+
+impl<T, A: Allocator> FusedIterator for Drain<'_, T, A> {}
+
+unsafe impl<T: Send, A: Allocator + Send> Send for Drain<'_, T, A> {}
+
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for Drain<'_, T, A> {}
+
+impl<T, A: Allocator> Drop for Drain<'_, T, A> {
+    fn drop(&mut self) {
+        // Closes the gap even if dropping an undrained element panics.
+        struct MoveTail<'r, 'a, T, A: Allocator>(&'r mut Drain<'a, T, A>);
+
+        impl<T, A: Allocator> Drop for MoveTail<'_, '_, T, A> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                unsafe {
+                    let vec = drain.vec.as_mut();
+                    let start = vec.len();
+                    let buf = vec.as_mut_ptr();
+
+                    if drain.tail_start != start {
+                        ptr::copy(buf.add(drain.tail_start), buf.add(start), drain.tail_len);
+                    }
+                    vec.set_len(start + drain.tail_len);
+                }
+            }
+        }
+
+        let guard = MoveTail(self);
+        let drain = &mut *guard.0;
+
+        // Mark the rest as yielded first, so a panicking destructor cannot
+        // lead to a second drop of the same element.
+        let (head, remaining) = (drain.head, drain.len());
+        drain.head = drain.end;
+
+        unsafe {
+            let rest = ptr::slice_from_raw_parts_mut(drain.buf().add(head), remaining);
+            ptr::drop_in_place(rest);
+        }
+    }
+}
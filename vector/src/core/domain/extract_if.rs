@@ -0,0 +1,96 @@
+#![allow(dead_code, unused_imports)]
+
+use core::{iter::FusedIterator, ptr};
+
+use super::vector::MyVector;
+use crate::shared::allocator::{Allocator, Global};
+
+// Removes and yields the elements matching `predicate`, one `next` at a time.
+// Elements before `index` have been visited and `deleted` of them removed, so
+// the kept ones sit `deleted` slots too far right until they are moved down.
+// The vector's length is zero meanwhile; dropping the iterator moves the
+// unvisited elements down and restores it, keeping them all.
+pub struct ExtractIf<'a, T, F, A: Allocator = Global> {
+    vec: &'a mut MyVector<T, A>,
+    index: usize,
+    deleted: usize,
+    original_len: usize,
+    predicate: F,
+}
+
+impl<'a, T, F, A: Allocator> ExtractIf<'a, T, F, A> {
+    pub(crate) fn new(vec: &'a mut MyVector<T, A>, predicate: F) -> Self {
+        let original_len = vec.len();
+        unsafe { vec.set_len(0) };
+
+        Self {
+            vec,
+            index: 0,
+            deleted: 0,
+            original_len,
+            predicate,
+        }
+    }
+}
+
+impl<T, F, A> Iterator for ExtractIf<'_, T, F, A>
+where
+    F: FnMut(&mut T) -> bool,
+    A: Allocator,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let buf = self.vec.as_mut_ptr();
+
+        while self.index < self.original_len {
+            unsafe {
+                let current = buf.add(self.index);
+
+                // `index` only moves on once `predicate` returned, so a panic
+                // leaves the current element to the unvisited rest.
+                let extract = (self.predicate)(&mut *current);
+                self.index += 1;
+
+                if extract {
+                    self.deleted += 1;
+                    return Some(ptr::read(current));
+                }
+
+                if self.deleted > 0 {
+                    ptr::copy_nonoverlapping(current, current.sub(self.deleted), 1);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.original_len - self.index))
+    }
+}
+
+impl<T, F, A> FusedIterator for ExtractIf<'_, T, F, A>
+where
+    F: FnMut(&mut T) -> bool,
+    A: Allocator,
+{
+}
+
+impl<T, F, A: Allocator> Drop for ExtractIf<'_, T, F, A> {
+    fn drop(&mut self) {
+        unsafe {
+            let buf = self.vec.as_mut_ptr();
+
+            if self.deleted > 0 {
+                ptr::copy(
+                    buf.add(self.index),
+                    buf.add(self.index - self.deleted),
+                    self.original_len - self.index,
+                );
+            }
+            self.vec.set_len(self.original_len - self.deleted);
+        }
+    }
+}
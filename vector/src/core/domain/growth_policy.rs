@@ -0,0 +1,31 @@
+// How `MyVector` picks a new capacity when it runs out of room. Every policy
+// returns at least the capacity that was asked for; they differ in how much
+// headroom they add on top, trading reallocations for unused memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrowthPolicy {
+    // Amortized O(1) pushes; up to half of the buffer can sit unused.
+    #[default]
+    Doubling,
+    // Amortized O(1) pushes with more reallocations; at most a third unused.
+    OneAndHalf,
+    // Bounded waste of `step - 1` slots, but O(n) reallocations over n pushes.
+    FixedIncrement(usize),
+    // No headroom at all: every push past the capacity reallocates.
+    Exact,
+}
+
+impl GrowthPolicy {
+    // Capacity to move to from `capacity` when at least `required` is needed.
+    // Saturates instead of overflowing; oversized requests are rejected later
+    // when the layout is computed.
+    pub fn grow(self, capacity: usize, required: usize) -> usize {
+        let proposed = match self {
+            GrowthPolicy::Doubling => capacity.saturating_mul(2),
+            GrowthPolicy::OneAndHalf => capacity.saturating_add(capacity / 2),
+            GrowthPolicy::FixedIncrement(step) => capacity.saturating_add(step),
+            GrowthPolicy::Exact => required,
+        };
+
+        proposed.max(required)
+    }
+}
@@ -1,101 +1,106 @@
-#![allow(dead_code, unused_imports, dead_code)]
+#![allow(dead_code, unused_imports)]
 
 use core::{iter::FusedIterator, ptr};
 
-use crate::shared::allocator::{deallocate_block, drop_contents};
+use crate::shared::allocator::{Allocator, Global, deallocate_block, drop_contents};
 
-pub struct IntoIter<T> {
+// Owns the buffer of a consumed `MyVector`. Elements in `head..tail` are
+// still to be yielded; indices rather than pointers keep zero-sized types on
+// the same path, since `buf.add(i)` is a no-op for them.
+pub struct IntoIter<T, A: Allocator = Global> {
     buf: *mut T,
-    ptr: *const T,
-    end: *const T,
+    head: usize,
+    tail: usize,
     capacity: usize,
+    allocator: A,
 }
 
-impl<T> IntoIter<T> {
-    pub fn new(ptr: *mut T, len: usize, capacity: usize) -> Self {
-        let end = unsafe {
-            if core::mem::size_of::<T>() == 0 {
-                (ptr as usize + len) as *mut T
-            } else {
-                ptr.add(len)
-            }
-        };
-
+impl<T, A: Allocator> IntoIter<T, A> {
+    pub(crate) fn new(ptr: *mut T, len: usize, capacity: usize, allocator: A) -> Self {
         Self {
             buf: ptr,
-            ptr,
-            end,
+            head: 0,
+            tail: len,
             capacity,
+            allocator,
         }
     }
 
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+
     pub fn len(&self) -> usize {
-        if core::mem::size_of::<T>() == 0 {
-            self.end as usize - self.ptr as usize
-        } else {
-            (self.end as usize - self.ptr as usize) / core::mem::size_of::<T>()
-        }
+        self.tail - self.head
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head == self.tail
     }
 }
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.ptr == self.end {
+        if self.head == self.tail {
             return None;
         }
 
-        unsafe {
-            if core::mem::size_of::<T>() == 0 {
-                self.ptr = (self.ptr as usize + 1) as *const T;
-                return Some(core::mem::zeroed());
-            }
+        let item = unsafe { ptr::read(self.buf.add(self.head)) };
+        self.head += 1;
+        Some(item)
+    }
 
-            let item = ptr::read(self.ptr);
-            self.ptr = self.ptr.add(1);
-            Some(item)
-        }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
     }
 }
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.ptr == self.end {
+        if self.head == self.tail {
             return None;
         }
 
-        unsafe {
-            if core::mem::size_of::<T>() == 0 {
-                self.end = (self.end as usize - 1) as *const T;
-                return Some(core::mem::zeroed());
-            }
-
-            let item = ptr::read(self.end.sub(1));
-            self.end = self.end.sub(1);
-            Some(item)
-        }
+        self.tail -= 1;
+        Some(unsafe { ptr::read(self.buf.add(self.tail)) })
     }
 }
 
-impl<T> ExactSizeIterator for IntoIter<T> {
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
     fn len(&self) -> usize {
         Self::len(self)
     }
 }
 
-impl<T> FusedIterator for IntoIter<T> {}
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
 
-unsafe impl<T: Send> Send for IntoIter<T> {}
+unsafe impl<T: Send, A: Allocator + Send> Send for IntoIter<T, A> {}
 
-unsafe impl<T: Sync> Sync for IntoIter<T> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for IntoIter<T, A> {}
 
-impl<T> Drop for IntoIter<T> {
+impl<T, A: Allocator> Drop for IntoIter<T, A> {
     fn drop(&mut self) {
-        drop_contents(self.ptr as *mut T, Self::len(self));
+        // Frees the buffer even if a destructor panics.
+        struct FreeBuffer<'a, T, A: Allocator>(&'a mut IntoIter<T, A>);
+
+        impl<T, A: Allocator> Drop for FreeBuffer<'_, T, A> {
+            fn drop(&mut self) {
+                let iter = &mut *self.0;
+                if iter.capacity > 0 {
+                    unsafe { deallocate_block(&iter.allocator, iter.buf, iter.capacity) };
+                }
+            }
+        }
+
+        // Mark the rest as yielded first, so nothing can be dropped twice.
+        let (head, len) = (self.head, Self::len(self));
+        self.head = self.tail;
 
-        if self.capacity > 0 && core::mem::size_of::<T>() > 0 {
-            deallocate_block(self.buf, self.capacity);
+        let guard = FreeBuffer(self);
+        unsafe {
+            drop_contents(guard.0.buf.add(head), len);
         }
     }
-}
\ No newline at end of file
+}
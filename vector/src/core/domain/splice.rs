@@ -0,0 +1,105 @@
+#![allow(dead_code, unused_imports)]
+
+use core::{alloc::Layout, iter::FusedIterator, ptr::NonNull};
+
+use super::{drain::Drain, vector::MyVector};
+use crate::shared::allocator::{AllocError, Allocator, Global};
+
+// Yields the elements of a range like `Drain`. When dropped, it writes the
+// replacement items into the range, then lets the inner `Drain` move the tail
+// back behind them.
+pub struct Splice<'a, I: Iterator, A: Allocator = Global> {
+    drain: Drain<'a, I::Item, A>,
+    replace_with: I,
+}
+
+impl<'a, I: Iterator, A: Allocator> Splice<'a, I, A> {
+    pub(crate) fn new(drain: Drain<'a, I::Item, A>, replace_with: I) -> Self {
+        Self {
+            drain,
+            replace_with,
+        }
+    }
+}
+
+impl<I: Iterator, A: Allocator> Iterator for Splice<'_, I, A> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<I: Iterator, A: Allocator> DoubleEndedIterator for Splice<'_, I, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<I: Iterator, A: Allocator> ExactSizeIterator for Splice<'_, I, A> {}
+
+impl<I: Iterator, A: Allocator> FusedIterator for Splice<'_, I, A> {}
+
+// Every step leaves the vector's length on the last written item, so if the
+// replacement iterator or a destructor panics, the inner `Drain` still closes
+// the gap and nothing is dropped twice. The tail moves at most once and the
+// vector's buffer is resized at most once, unless the iterator reports an
+// exact size that turns out too small. Leftover items from an iterator
+// without an exact size go through a temporary buffer from the same allocator.
+impl<I: Iterator, A: Allocator> Drop for Splice<'_, I, A> {
+    fn drop(&mut self) {
+        self.drain.by_ref().for_each(drop);
+
+        if !self.drain.fill(&mut self.replace_with) {
+            return;
+        }
+
+        // An exact size hint lets the tail move straight to its final place.
+        let (lower, upper) = self.replace_with.size_hint();
+        if lower > 0 && upper == Some(lower) {
+            self.drain.move_tail(lower);
+            if !self.drain.fill(&mut self.replace_with) {
+                return;
+            }
+        }
+
+        // Otherwise the rest is collected first, so it is known how far the
+        // tail must move.
+        let mut rest = MyVector::new_in(VecAllocator(self.drain.allocator_ptr()));
+        rest.extend(self.replace_with.by_ref());
+
+        if !rest.is_empty() {
+            self.drain.move_tail(rest.len());
+            let filled = self.drain.fill(&mut rest.into_iter());
+            debug_assert!(filled);
+        }
+    }
+}
+
+// The vector's own allocator, for the temporary buffer. A `&A` held across
+// `move_tail` and `fill` would alias the `&mut` borrows of the vector they
+// take, so every call goes through the pointer instead.
+struct VecAllocator<A>(*const A);
+
+unsafe impl<A: Allocator> Allocator for VecAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        unsafe { (*self.0).allocate(layout) }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { (*self.0).deallocate(ptr, layout) }
+    }
+
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        unsafe { (*self.0).reallocate(ptr, old_layout, new_layout) }
+    }
+}
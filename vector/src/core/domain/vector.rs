@@ -1,49 +1,125 @@
-#![allow(dead_code, unused_imports, dead_code)]
+#![allow(dead_code, unused_imports)]
+
+extern crate alloc;
 
 use core::{
-    mem,
-    ops::{Deref, DerefMut},
-    ptr::{self},
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::{self, ManuallyDrop},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds},
+    ptr::{self, NonNull},
     slice::{Iter, IterMut},
 };
 
-use super::into_iter::IntoIter;
-use crate::{println, shared};
-use shared::allocator::{allocate_block, deallocate_block, drop_contents};
+use super::{
+    drain::Drain, extract_if::ExtractIf, growth_policy::GrowthPolicy, into_iter::IntoIter,
+    splice::Splice,
+};
+use crate::shared;
+use alloc::vec::Vec;
+use shared::allocator::{
+    Allocator, Global, TryReserveError, deallocate_block, drop_contents, handle_reserve,
+    try_allocate_block, try_reallocate_block,
+};
 
-pub struct MyVector<T> {
+pub struct MyVector<T, A: Allocator = Global> {
     ptr: *mut T,
     len: usize,
     capacity: usize,
+    policy: GrowthPolicy,
+    allocator: A,
 }
 
 impl<T> MyVector<T> {
     pub fn new() -> Self {
-        Self::with_capacity(0)
+        Self::new_in(Global)
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
-        let ptr = if capacity == 0 {
-            ptr::null_mut()
+        Self::with_capacity_in(capacity, Global)
+    }
+
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
+
+    // Backs `myvec![elem; n]`: `n` clones, the last being `elem` itself.
+    pub fn from_elem(elem: T, n: usize) -> Self
+    where
+        T: Clone,
+    {
+        let mut vec = Self::with_capacity(n);
+        vec.resize(n, elem);
+        vec
+    }
+}
+
+impl<T, A: Allocator> MyVector<T, A> {
+    // Zero-sized elements need no storage: the buffer stays a dangling pointer,
+    // nothing is ever allocated and the capacity is unbounded.
+    const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+    pub fn new_in(allocator: A) -> Self {
+        Self::with_capacity_in(0, allocator)
+    }
+
+    pub fn with_capacity_in(capacity: usize, allocator: A) -> Self {
+        handle_reserve(Self::try_with_capacity_in(capacity, allocator))
+    }
+
+    pub fn try_with_capacity_in(capacity: usize, allocator: A) -> Result<Self, TryReserveError> {
+        let ptr = if capacity == 0 || Self::IS_ZST {
+            NonNull::dangling().as_ptr()
         } else {
-            allocate_block(capacity)
+            try_allocate_block(&allocator, capacity)?
         };
-        Self {
+        Ok(Self {
             ptr,
             len: 0,
-            capacity,
-        }
+            capacity: if Self::IS_ZST { 0 } else { capacity },
+            policy: GrowthPolicy::default(),
+            allocator,
+        })
+    }
+
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+
+    // Reaches the allocator without a reference to the whole vector, for
+    // code that keeps using it while the vector is borrowed mutably.
+    pub(crate) fn allocator_ptr(this: *const Self) -> *const A {
+        unsafe { &raw const (*this).allocator }
+    }
+
+    pub fn growth_policy(&self) -> GrowthPolicy {
+        self.policy
+    }
+
+    // Applies to every later growth; the current capacity is kept.
+    pub fn set_growth_policy(&mut self, policy: GrowthPolicy) {
+        self.policy = policy;
     }
 
     pub fn push(&mut self, item: T) {
-        if self.len == self.capacity {
-            self.grow();
+        handle_reserve(self.try_push(item).map_err(|(_, err)| err))
+    }
+
+    // On error `item` is handed back and the vector is untouched.
+    pub fn try_push(&mut self, item: T) -> Result<(), (T, TryReserveError)> {
+        if self.len == self.capacity()
+            && let Err(err) = self.try_grow()
+        {
+            return Err((item, err));
         }
 
         unsafe {
             ptr::write(self.ptr.add(self.len), item);
         }
         self.len += 1;
+        Ok(())
     }
 
     pub fn pop(&mut self) -> Option<T> {
@@ -56,12 +132,19 @@ impl<T> MyVector<T> {
     }
 
     pub fn insert(&mut self, index: usize, item: T) {
+        handle_reserve(self.try_insert(index, item).map_err(|(_, err)| err))
+    }
+
+    // On error `item` is handed back and the vector is untouched.
+    pub fn try_insert(&mut self, index: usize, item: T) -> Result<(), (T, TryReserveError)> {
         if index > self.len {
             panic!("Index out of bounds");
         }
 
-        if self.len == self.capacity {
-            self.grow();
+        if self.len == self.capacity()
+            && let Err(err) = self.try_grow()
+        {
+            return Err((item, err));
         }
 
         unsafe {
@@ -75,6 +158,7 @@ impl<T> MyVector<T> {
         }
 
         self.len += 1;
+        Ok(())
     }
 
     pub fn remove(&mut self, index: usize) -> T {
@@ -96,70 +180,475 @@ impl<T> MyVector<T> {
         }
     }
 
-    pub fn extend(&mut self, iter: impl IntoIterator<Item = T>) {
-        let iter = iter.into_iter();
-        let count = iter.size_hint().0;
-        self.reserve(count);
+    // On error the elements appended so far are dropped again, so the
+    // contents are as before; the capacity may have grown. If `next` panics,
+    // the elements appended so far stay.
+    pub fn try_extend(&mut self, iter: impl IntoIterator<Item = T>) -> Result<(), TryReserveError> {
+        let len = self.len;
+        let result = self.try_extend_iter(iter.into_iter());
 
-        for item in iter {
-            self.push(item);
+        if result.is_err() {
+            self.truncate(len);
         }
+
+        result
     }
 
-    fn reallocate(&mut self, new_capacity: usize) {
-        let new_ptr = if new_capacity == 0 {
-            ptr::null_mut()
-        } else {
-            allocate_block(new_capacity)
-        };
+    // Fills the spare capacity, then grows by the iterator's lower bound
+    // plus one and repeats. The guard stores the length once per batch, and
+    // also when `next` panics halfway through one.
+    fn try_extend_iter(
+        &mut self,
+        mut iter: impl Iterator<Item = T>,
+    ) -> Result<(), TryReserveError> {
+        self.try_reserve(iter.size_hint().0)?;
+
+        loop {
+            let (ptr, capacity) = (self.ptr, self.capacity());
+            let mut len = SetLenOnDrop::new(&mut self.len);
+
+            while len.current() < capacity {
+                let Some(item) = iter.next() else {
+                    return Ok(());
+                };
+                unsafe {
+                    ptr::write(ptr.add(len.current()), item);
+                }
+                len.increment();
+            }
+            drop(len);
 
-        if self.capacity > 0 {
+            let Some(item) = iter.next() else {
+                return Ok(());
+            };
+            self.try_reserve(iter.size_hint().0.saturating_add(1))?;
             unsafe {
-                ptr::copy_nonoverlapping(self.ptr, new_ptr, self.len);
+                ptr::write(self.ptr.add(self.len), item);
             }
-
-            deallocate_block(self.ptr, self.capacity);
+            self.len += 1;
         }
+    }
 
-        self.ptr = new_ptr;
-        self.capacity = new_capacity;
+    fn reallocate(&mut self, new_capacity: usize) {
+        handle_reserve(self.try_reallocate(new_capacity))
     }
 
-    fn grow(&mut self) {
-        let new_capacity = if self.capacity == 0 {
-            1
-        } else {
-            self.capacity * 2
+    // Leaves `ptr` and `capacity` untouched unless the new block was obtained.
+    // Moving between two nonempty blocks goes through the allocator's
+    // `reallocate`, which may resize in place instead of copying.
+    fn try_reallocate(&mut self, new_capacity: usize) -> Result<(), TryReserveError> {
+        let new_ptr = match (self.capacity, new_capacity) {
+            (0, 0) => NonNull::dangling().as_ptr(),
+            (0, _) => try_allocate_block(&self.allocator, new_capacity)?,
+            (_, 0) => {
+                unsafe { deallocate_block(&self.allocator, self.ptr, self.capacity) };
+                NonNull::dangling().as_ptr()
+            }
+            _ => unsafe {
+                try_reallocate_block(&self.allocator, self.ptr, self.capacity, new_capacity)?
+            },
         };
 
-        self.reallocate(new_capacity);
+        self.ptr = new_ptr;
+        self.capacity = new_capacity;
+        Ok(())
+    }
+
+    fn try_grow(&mut self) -> Result<(), TryReserveError> {
+        self.try_reserve(1)
     }
 
     pub fn reserve(&mut self, additional: usize) {
-        let required_capacity = self.len + additional;
-        if required_capacity > self.capacity {
-            self.reallocate(required_capacity.next_power_of_two());
+        handle_reserve(self.try_reserve(additional))
+    }
+
+    // Room for `additional` more elements, with headroom from the growth policy.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required_capacity = self.required_capacity(additional)?;
+
+        if required_capacity > self.capacity() {
+            let new_capacity = self.policy.grow(self.capacity, required_capacity);
+            self.try_reallocate(new_capacity)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn reserve_exact(&mut self, additional: usize) {
+        handle_reserve(self.try_reserve_exact(additional))
+    }
+
+    // Room for exactly `additional` more elements, ignoring the growth policy.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required_capacity = self.required_capacity(additional)?;
+
+        if required_capacity > self.capacity() {
+            self.try_reallocate(required_capacity)?;
         }
+
+        Ok(())
+    }
+
+    fn required_capacity(&self, additional: usize) -> Result<usize, TryReserveError> {
+        self.len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)
     }
 
     pub fn shrink_to_fit(&mut self) {
-        let new_capacity = if self.len == 0 {
-            0
+        self.shrink_to(0);
+    }
+
+    // Lowers the capacity to `max(len, min_capacity)`; never grows.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let new_capacity = self.len.max(min_capacity);
+
+        if !Self::IS_ZST && new_capacity < self.capacity {
+            self.reallocate(new_capacity);
+        }
+    }
+
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        if index >= self.len {
+            panic!("Index out of bounds");
+        }
+
+        unsafe {
+            let item = ptr::read(self.ptr.add(index));
+
+            // The last element fills the hole; for the last index this is a
+            // copy onto itself.
+            ptr::copy(self.ptr.add(self.len - 1), self.ptr.add(index), 1);
+
+            self.len -= 1;
+            item
+        }
+    }
+
+    // Drops everything from `len` on; no-op if the vector is not longer.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        let remaining = self.len - len;
+        // Shorten first, so a panicking destructor cannot leave dropped
+        // elements inside the vector; the remaining ones are still dropped.
+        self.len = len;
+        unsafe {
+            drop_contents(self.ptr.add(len), remaining);
+        }
+    }
+
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        handle_reserve(self.try_resize(new_len, value))
+    }
+
+    // On error `value` is dropped and the vector is untouched.
+    pub fn try_resize(&mut self, new_len: usize, value: T) -> Result<(), TryReserveError>
+    where
+        T: Clone,
+    {
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return Ok(());
+        }
+
+        // The last slot takes `value` itself, so reserve for it up front.
+        self.try_reserve(new_len - self.len)?;
+        self.try_extend_with(new_len - self.len - 1, || value.clone())?;
+
+        unsafe {
+            ptr::write(self.ptr.add(self.len), value);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn resize_with(&mut self, new_len: usize, f: impl FnMut() -> T) {
+        handle_reserve(self.try_resize_with(new_len, f))
+    }
+
+    // On error `f` is never called and the vector is untouched.
+    pub fn try_resize_with(
+        &mut self,
+        new_len: usize,
+        f: impl FnMut() -> T,
+    ) -> Result<(), TryReserveError> {
+        if new_len > self.len {
+            self.try_extend_with(new_len - self.len, f)
         } else {
-            self.len.next_power_of_two()
+            self.truncate(new_len);
+            Ok(())
+        }
+    }
+
+    // Reserves once; if `f` panics, the guard keeps the elements made so far.
+    fn try_extend_with(
+        &mut self,
+        count: usize,
+        mut f: impl FnMut() -> T,
+    ) -> Result<(), TryReserveError> {
+        self.try_reserve(count)?;
+
+        let ptr = self.ptr;
+        let mut len = SetLenOnDrop::new(&mut self.len);
+        for _ in 0..count {
+            unsafe {
+                ptr::write(ptr.add(len.current()), f());
+            }
+            len.increment();
+        }
+        Ok(())
+    }
+
+    pub fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        handle_reserve(self.try_extend_from_slice(items))
+    }
+
+    // On error nothing is cloned and the vector is untouched.
+    pub fn try_extend_from_slice(&mut self, items: &[T]) -> Result<(), TryReserveError>
+    where
+        T: Clone,
+    {
+        let mut items = items.iter();
+        self.try_extend_with(items.len(), || items.next().unwrap().clone())
+    }
+
+    // Moves every element of `other` to the end of `self`, leaving `other`
+    // empty with its capacity intact.
+    pub fn append(&mut self, other: &mut Self) {
+        handle_reserve(self.try_append(other))
+    }
+
+    // On error both vectors are untouched.
+    pub fn try_append(&mut self, other: &mut Self) -> Result<(), TryReserveError> {
+        self.try_reserve(other.len)?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(other.ptr, self.ptr.add(self.len), other.len);
+        }
+        self.len += other.len;
+        other.len = 0;
+        Ok(())
+    }
+
+    // Returns `at..len` in a new vector on a clone of the allocator, with the
+    // same growth policy; `self` keeps `0..at` and its capacity.
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        handle_reserve(self.try_split_off(at))
+    }
+
+    // On error `self` is untouched.
+    pub fn try_split_off(&mut self, at: usize) -> Result<Self, TryReserveError>
+    where
+        A: Clone,
+    {
+        if at > self.len {
+            panic!("Index out of bounds");
+        }
+
+        let other_len = self.len - at;
+        let mut other = Self::try_with_capacity_in(other_len, self.allocator.clone())?;
+        other.policy = self.policy;
+
+        unsafe {
+            ptr::copy_nonoverlapping(self.ptr.add(at), other.ptr, other_len);
+        }
+        self.len = at;
+        other.len = other_len;
+        Ok(other)
+    }
+
+    // Removes `range` and yields its elements. Whatever is not consumed is
+    // dropped with the iterator, and the tail then moves down to close the gap.
+    pub fn drain(&mut self, range: impl RangeBounds<usize>) -> Drain<'_, T, A> {
+        let len = self.len;
+        let Range { start, end } = slice_range(range, len);
+
+        self.len = start;
+        Drain::new(self, start, end, len)
+    }
+
+    // Replaces `range` with the items of `replace_with` and yields the removed
+    // elements. The replacement happens when the returned `Splice` is dropped.
+    pub fn splice<I: IntoIterator<Item = T>>(
+        &mut self,
+        range: impl RangeBounds<usize>,
+        replace_with: I,
+    ) -> Splice<'_, I::IntoIter, A> {
+        Splice::new(self.drain(range), replace_with.into_iter())
+    }
+
+    // Lazily removes and yields the elements for which `predicate` holds.
+    // Elements not reached before the iterator is dropped are kept.
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, predicate: F) -> ExtractIf<'_, T, F, A> {
+        ExtractIf::new(self, predicate)
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        self.retain_mut(|item| keep(item));
+    }
+
+    // Keeps the order of the retained elements and visits each element
+    // exactly once, even when `keep` or a destructor panics.
+    pub fn retain_mut(&mut self, mut keep: impl FnMut(&mut T) -> bool) {
+        // Elements before `processed` have been visited; `deleted` of them
+        // were dropped, so the kept ones sit `deleted` slots too far right.
+        struct Guard<'a, T, A: Allocator> {
+            vec: &'a mut MyVector<T, A>,
+            processed: usize,
+            deleted: usize,
+            original_len: usize,
+        }
+
+        impl<T, A: Allocator> Drop for Guard<'_, T, A> {
+            fn drop(&mut self) {
+                unsafe {
+                    let ptr = self.vec.ptr;
+                    if self.deleted > 0 {
+                        ptr::copy(
+                            ptr.add(self.processed),
+                            ptr.add(self.processed - self.deleted),
+                            self.original_len - self.processed,
+                        );
+                    }
+                }
+                self.vec.len = self.original_len - self.deleted;
+            }
+        }
+
+        let original_len = self.len;
+        // Hidden while `keep` runs; the guard restores the length.
+        self.len = 0;
+
+        let mut guard = Guard {
+            vec: self,
+            processed: 0,
+            deleted: 0,
+            original_len,
         };
 
-        if new_capacity < self.capacity {
-            self.reallocate(new_capacity);
+        while guard.processed != original_len {
+            let current = unsafe { guard.vec.ptr.add(guard.processed) };
+
+            if !keep(unsafe { &mut *current }) {
+                guard.processed += 1;
+                guard.deleted += 1;
+                unsafe { ptr::drop_in_place(current) };
+                continue;
+            }
+
+            if guard.deleted > 0 {
+                unsafe {
+                    ptr::copy_nonoverlapping(current, current.sub(guard.deleted), 1);
+                }
+            }
+            guard.processed += 1;
         }
     }
 
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K: PartialEq>(&mut self, mut key: impl FnMut(&mut T) -> K) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    // Removes each element for which `same_bucket(element, previous_kept)`
+    // holds, so only the first of every run of equal elements stays.
+    pub fn dedup_by(&mut self, mut same_bucket: impl FnMut(&mut T, &mut T) -> bool) {
+        // Elements before `write` are kept, `write..read` are holes and
+        // `read..len` are unvisited; a panic closes the holes.
+        struct FillGap<'a, T, A: Allocator> {
+            vec: &'a mut MyVector<T, A>,
+            read: usize,
+            write: usize,
+            original_len: usize,
+        }
+
+        impl<T, A: Allocator> Drop for FillGap<'_, T, A> {
+            fn drop(&mut self) {
+                let unvisited = self.original_len - self.read;
+                unsafe {
+                    let ptr = self.vec.ptr;
+                    ptr::copy(ptr.add(self.read), ptr.add(self.write), unvisited);
+                }
+                self.vec.len = self.write + unvisited;
+            }
+        }
+
+        let original_len = self.len;
+        if original_len <= 1 {
+            return;
+        }
+        self.len = 0;
+
+        let mut gap = FillGap {
+            vec: self,
+            read: 1,
+            write: 1,
+            original_len,
+        };
+
+        while gap.read != original_len {
+            unsafe {
+                let current = gap.vec.ptr.add(gap.read);
+                let previous = gap.vec.ptr.add(gap.write - 1);
+
+                if same_bucket(&mut *current, &mut *previous) {
+                    gap.read += 1;
+                    ptr::drop_in_place(current);
+                } else {
+                    ptr::copy(current, gap.vec.ptr.add(gap.write), 1);
+                    gap.write += 1;
+                    gap.read += 1;
+                }
+            }
+        }
+    }
+
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr
+    }
+
+    /// Sets the length without dropping or initializing anything.
+    ///
+    /// # Safety
+    ///
+    /// `new_len` must not exceed the capacity, and the first `new_len`
+    /// elements must be initialized.
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        self.len = new_len;
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
 
     pub fn capacity(&self) -> usize {
-        self.capacity
+        if Self::IS_ZST {
+            usize::MAX
+        } else {
+            self.capacity
+        }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -167,39 +656,286 @@ impl<T> MyVector<T> {
     }
 
     pub fn is_full(&self) -> bool {
-        self.len == self.capacity
+        self.len == self.capacity()
     }
 
     pub fn clear(&mut self) {
-        drop_contents(self.ptr, self.len);
-        self.len = 0;
+        self.truncate(0);
     }
 
     fn clean_up(&mut self) {
-        if self.ptr.is_null() {
-            return;
+        // Frees the buffer even if a destructor panics.
+        struct FreeBuffer<'a, T, A: Allocator>(&'a mut MyVector<T, A>);
+
+        impl<T, A: Allocator> Drop for FreeBuffer<'_, T, A> {
+            fn drop(&mut self) {
+                let vec = &mut *self.0;
+                if vec.capacity > 0 {
+                    unsafe { deallocate_block(&vec.allocator, vec.ptr, vec.capacity) };
+                }
+            }
+        }
+
+        let len = mem::replace(&mut self.len, 0);
+        let guard = FreeBuffer(self);
+        unsafe {
+            drop_contents(guard.0.ptr, len);
+        }
+    }
+}
+
+// Counts elements written past `len` in a local and stores the total when
+// dropped, including during unwinding, so `len` never covers a slot that was
+// not written and never misses one that was.
+struct SetLenOnDrop<'a> {
+    len: &'a mut usize,
+    local_len: usize,
+}
+
+impl<'a> SetLenOnDrop<'a> {
+    fn new(len: &'a mut usize) -> Self {
+        let local_len = *len;
+        Self { len, local_len }
+    }
+
+    fn current(&self) -> usize {
+        self.local_len
+    }
+
+    fn increment(&mut self) {
+        self.local_len += 1;
+    }
+}
+
+impl Drop for SetLenOnDrop<'_> {
+    fn drop(&mut self) {
+        *self.len = self.local_len;
+    }
+}
+
+// Resolves `range` against a slice of `len` elements, panicking like slice
+// indexing on a reversed or out-of-bounds range.
+fn slice_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("Range start overflows"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("Range end overflows"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    if start > end || end > len {
+        panic!("Range out of bounds");
+    }
+    start..end
+}
+
+impl<T, A: Allocator + Default> Default for MyVector<T, A> {
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
+
+// The copy gets a clone of the allocator, the same growth policy and a
+// capacity of exactly `len`.
+impl<T: Clone, A: Allocator + Clone> Clone for MyVector<T, A> {
+    fn clone(&self) -> Self {
+        let mut vec = Self::with_capacity_in(self.len, self.allocator.clone());
+        vec.policy = self.policy;
+        vec.extend_from_slice(self);
+        vec
+    }
+
+    // Reuses the existing buffer and elements where it can.
+    fn clone_from(&mut self, source: &Self) {
+        self.truncate(source.len);
+        let (init, tail) = source.split_at(self.len);
+        self.clone_from_slice(init);
+        self.extend_from_slice(tail);
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for MyVector<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+// Comparisons and hashing go through the element slice, so they agree with
+// `[T]` and `Vec<T>`, which `Borrow<[T]>` relies on.
+impl<T, U, A1, A2> PartialEq<MyVector<U, A2>> for MyVector<T, A1>
+where
+    T: PartialEq<U>,
+    A1: Allocator,
+    A2: Allocator,
+{
+    fn eq(&self, other: &MyVector<U, A2>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<[U]> for MyVector<T, A> {
+    fn eq(&self, other: &[U]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<&[U]> for MyVector<T, A> {
+    fn eq(&self, other: &&[U]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator, const N: usize> PartialEq<[U; N]> for MyVector<T, A> {
+    fn eq(&self, other: &[U; N]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<Vec<U>> for MyVector<T, A> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for MyVector<T, A> {}
+
+// Lexicographic, like slices.
+impl<T, A1, A2> PartialOrd<MyVector<T, A2>> for MyVector<T, A1>
+where
+    T: PartialOrd,
+    A1: Allocator,
+    A2: Allocator,
+{
+    fn partial_cmp(&self, other: &MyVector<T, A2>) -> Option<Ordering> {
+        self[..].partial_cmp(&other[..])
+    }
+}
+
+impl<T: Ord, A: Allocator> Ord for MyVector<T, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self[..].cmp(&other[..])
+    }
+}
+
+impl<T: Hash, A: Allocator> Hash for MyVector<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl<T> FromIterator<T> for MyVector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for MyVector<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        handle_reserve(self.try_extend(iter))
+    }
+}
+
+impl<'a, T: Copy + 'a, A: Allocator> Extend<&'a T> for MyVector<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for MyVector<T> {
+    fn from(array: [T; N]) -> Self {
+        let array = ManuallyDrop::new(array);
+        let mut vec = Self::with_capacity(N);
+
+        unsafe {
+            ptr::copy_nonoverlapping(array.as_ptr(), vec.ptr, N);
+        }
+        vec.len = N;
+        vec
+    }
+}
+
+impl<T: Clone> From<&[T]> for MyVector<T> {
+    fn from(items: &[T]) -> Self {
+        let mut vec = Self::with_capacity(items.len());
+        vec.extend_from_slice(items);
+        vec
+    }
+}
+
+// Takes over the `Vec`'s buffer without copying. `Vec` allocates through the
+// global allocator with `Layout::array::<T>(capacity)`, exactly like `Global`.
+impl<T> From<Vec<T>> for MyVector<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+
+        Self {
+            ptr: vec.as_mut_ptr(),
+            len: vec.len(),
+            capacity: if Self::IS_ZST { 0 } else { vec.capacity() },
+            policy: GrowthPolicy::default(),
+            allocator: Global,
         }
+    }
+}
 
-        drop_contents(self.ptr, self.len);
-        deallocate_block(self.ptr, self.capacity);
+impl<T, A: Allocator> AsRef<[T]> for MyVector<T, A> {
+    fn as_ref(&self) -> &[T] {
+        self
     }
 }
 
-impl<T> IntoIterator for MyVector<T> {
+impl<T, A: Allocator> AsMut<[T]> for MyVector<T, A> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, A: Allocator> Borrow<[T]> for MyVector<T, A> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, A: Allocator> BorrowMut<[T]> for MyVector<T, A> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+// Mirrors `vec!`: `myvec![]`, `myvec![a, b, c]` and `myvec![elem; n]`.
+#[macro_export]
+macro_rules! myvec {
+    () => (
+        $crate::core::domain::vector::MyVector::new()
+    );
+    ($elem:expr; $n:expr) => (
+        $crate::core::domain::vector::MyVector::from_elem($elem, $n)
+    );
+    ($($x:expr),+ $(,)?) => (
+        $crate::core::domain::vector::MyVector::from([$($x),+])
+    );
+}
+
+impl<T, A: Allocator> IntoIterator for MyVector<T, A> {
     type Item = T;
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, A>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let len = self.len;
-        let capacity = self.capacity;
-        let ptr = self.ptr;
+        // The iterator takes over the buffer and the allocator that owns it.
+        let vec = ManuallyDrop::new(self);
+        let allocator = unsafe { ptr::read(&vec.allocator) };
 
-        mem::forget(self);
-        IntoIter::new(ptr, len, capacity)
+        IntoIter::new(vec.ptr, vec.len, vec.capacity, allocator)
     }
 }
 
-impl<'a, T> IntoIterator for &'a MyVector<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a MyVector<T, A> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -208,7 +944,7 @@ impl<'a, T> IntoIterator for &'a MyVector<T> {
     }
 }
 
-impl<'a, T> IntoIterator for &'a mut MyVector<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a mut MyVector<T, A> {
     type Item = &'a mut T;
     type IntoIter = IterMut<'a, T>;
 
@@ -217,7 +953,7 @@ impl<'a, T> IntoIterator for &'a mut MyVector<T> {
     }
 }
 
-impl<T> Deref for MyVector<T> {
+impl<T, A: Allocator> Deref for MyVector<T, A> {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
@@ -225,15 +961,14 @@ impl<T> Deref for MyVector<T> {
     }
 }
 
-impl<T> DerefMut for MyVector<T> {
+impl<T, A: Allocator> DerefMut for MyVector<T, A> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         unsafe { core::slice::from_raw_parts_mut(self.ptr, self.len) }
     }
 }
 
-impl<T> Drop for MyVector<T> {
+impl<T, A: Allocator> Drop for MyVector<T, A> {
     fn drop(&mut self) {
-        println!("Vector dropped");
         Self::clean_up(self);
     }
 }
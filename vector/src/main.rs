@@ -1,4 +1,88 @@
-use vector::core::domain::vector::MyVector;
+use std::{
+    alloc::Layout,
+    cell::{Cell, UnsafeCell},
+    ptr::NonNull,
+};
+
+use vector::{
+    core::domain::{growth_policy::GrowthPolicy, vector::MyVector},
+    shared::{
+        allocator::{AllocError, Allocator},
+        instrumented::Instrumented,
+    },
+};
+
+const ARENA_SIZE: usize = 1024;
+
+const OVERHEAD_PUSHES: usize = 100_000;
+const POLICIES: &[(&str, GrowthPolicy)] = &[
+    ("Doubling", GrowthPolicy::Doubling),
+    ("1.5x", GrowthPolicy::OneAndHalf),
+    ("+4096", GrowthPolicy::FixedIncrement(4096)),
+    ("Exact", GrowthPolicy::Exact),
+];
+
+// Bump allocator over a fixed buffer. Frees are no-ops; the memory comes back
+// when the arena itself is dropped, so vectors borrow it by reference.
+struct Arena {
+    buffer: UnsafeCell<[u8; ARENA_SIZE]>,
+    used: Cell<usize>,
+}
+
+impl Arena {
+    fn new() -> Self {
+        Self {
+            buffer: UnsafeCell::new([0; ARENA_SIZE]),
+            used: Cell::new(0),
+        }
+    }
+}
+
+unsafe impl Allocator for Arena {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        let base = self.buffer.get() as *mut u8;
+        let used = self.used.get();
+        let start = used + unsafe { base.add(used) }.align_offset(layout.align());
+        let end = start + layout.size();
+
+        if end > ARENA_SIZE {
+            return Err(AllocError);
+        }
+
+        self.used.set(end);
+        NonNull::new(unsafe { base.add(start) }).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+
+    // The most recent block can grow or shrink in place by moving the bump
+    // offset; anything else falls back to allocate-copy.
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        let base = self.buffer.get() as *mut u8;
+        let start = ptr.as_ptr() as usize - base as usize;
+
+        if start + old_layout.size() == self.used.get() {
+            let end = start + new_layout.size();
+            if end > ARENA_SIZE {
+                return Err(AllocError);
+            }
+            self.used.set(end);
+            return Ok(ptr);
+        }
+
+        let new_ptr = self.allocate(new_layout)?;
+        unsafe {
+            let count = old_layout.size().min(new_layout.size());
+            std::ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), count);
+        }
+        Ok(new_ptr)
+    }
+}
 
 fn main() {
     let mut vec = MyVector::with_capacity(2);
@@ -19,7 +103,7 @@ fn main() {
     for i in 0..vec.len() {
         print!("{} ", vec[i]);
     }
-    println!("");
+    println!();
 
     let removed = vec.remove(1);
     println!("Remove: {}", removed);
@@ -28,7 +112,7 @@ fn main() {
     for x in &vec {
         print!("{} ", x);
     }
-    println!("");
+    println!();
 
     println!("Extend [4, 5]");
     vec.extend([4, 5]);
@@ -37,7 +121,7 @@ fn main() {
     for x in vec.iter().rev() {
         print!("{} ", x);
     }
-    println!("");
+    println!();
 
     vec.shrink_to_fit();
     println!("Shrink: len={}, cap={}", vec.len(), vec.capacity());
@@ -46,11 +130,114 @@ fn main() {
     while let Some(x) = vec.pop() {
         print!("{} ", x);
     }
-    println!("");
+    println!();
 
     println!("Push 100");
     vec.push(100);
 
     vec.clear();
     println!("Clear: len={}, is_empty={}", vec.len(), vec.is_empty());
+
+    let arena = Arena::new();
+    let mut numbers = MyVector::new_in(&arena);
+    for i in 1..=10 {
+        numbers.push(i);
+    }
+    println!(
+        "Arena: len={}, cap={}, used={}/{} bytes",
+        numbers.len(),
+        numbers.capacity(),
+        arena.used.get(),
+        ARENA_SIZE
+    );
+
+    let sum: i32 = numbers.into_iter().sum();
+    println!("Arena sum: {}", sum);
+
+    match MyVector::<u64>::try_with_capacity(usize::MAX) {
+        Ok(_) => println!("try_with_capacity(usize::MAX): ok"),
+        Err(err) => println!("try_with_capacity(usize::MAX): {}", err),
+    }
+
+    // A second arena, filled until a push fails: the error leaves the
+    // vector as it was.
+    let arena = Arena::new();
+    let mut numbers = MyVector::new_in(&arena);
+    let err = loop {
+        if let Err((_, err)) = numbers.try_push(numbers.len() as u64) {
+            break err;
+        }
+    };
+    println!(
+        "try_push: {} at len={}, cap={}, last={:?}",
+        err,
+        numbers.len(),
+        numbers.capacity(),
+        numbers.last()
+    );
+
+    let before = numbers.len();
+    let result = numbers.try_extend(0..1000);
+    println!(
+        "try_extend: {:?}, len unchanged: {}",
+        result.map_err(|err| err.to_string()),
+        numbers.len() == before
+    );
+
+    report_overhead();
+}
+
+// Pushes the same number of `u64`s under each growth policy and reports how
+// often the buffer moved and how much of it ends up unused.
+fn report_overhead() {
+    println!();
+    println!("=== MEMORY OVERHEAD ({} u64 pushes) ===", OVERHEAD_PUSHES);
+    println!(
+        "{:<10} {:>9} {:>8} {:>12} {:>9}",
+        "Policy", "Capacity", "Grows", "Unused (B)", "Overhead"
+    );
+
+    let mut events = Vec::new();
+
+    for &(name, policy) in POLICIES {
+        let instrumented = Instrumented::new();
+        let mut vec = MyVector::new_in(&instrumented);
+        vec.set_growth_policy(policy);
+
+        for i in 0..OVERHEAD_PUSHES {
+            vec.push(i as u64);
+        }
+
+        let stats = instrumented.stats();
+        let grows = stats.allocations + stats.reallocations;
+        let unused = (vec.capacity() - vec.len()) * size_of::<u64>();
+        println!(
+            "{:<10} {:>9} {:>8} {:>12} {:>8.1}%",
+            name,
+            vec.capacity(),
+            grows,
+            unused,
+            100.0 * unused as f64 / (vec.len() * size_of::<u64>()) as f64
+        );
+
+        if policy == GrowthPolicy::Doubling {
+            vec.shrink_to(vec.len() + 1000);
+            println!(
+                "{:<10} {:>9} after shrink_to(len + 1000)",
+                "",
+                vec.capacity()
+            );
+            vec.reserve_exact(2000);
+            println!("{:<10} {:>9} after reserve_exact(2000)", "", vec.capacity());
+        }
+
+        drop(vec);
+        events.push((name, instrumented.stats()));
+    }
+
+    println!();
+    println!("=== ALLOCATION EVENTS ===");
+    for (name, stats) in events {
+        println!("{:<10} {}", name, stats);
+    }
 }
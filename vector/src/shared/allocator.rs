@@ -1,31 +1,227 @@
-use core::ptr;
+use core::{fmt, ptr, ptr::NonNull};
 
 extern crate alloc;
 
-use alloc::alloc::{Layout, alloc, dealloc, handle_alloc_error};
+use alloc::alloc::{Layout, alloc, dealloc, handle_alloc_error, realloc};
 
-pub fn allocate_block<T>(size: usize) -> *mut T {
-    let layout = Layout::array::<T>(size).unwrap();
-    unsafe {
-        let ptr = alloc(layout) as *mut T;
-        if ptr.is_null() {
-            handle_alloc_error(layout);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+// Why a fallible growing operation gave up. Either way the elements of the
+// vector it was called on are left as they were.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReserveError {
+    // The requested capacity does not fit in `isize::MAX` bytes.
+    CapacityOverflow,
+    // The allocator refused a request of this layout.
+    AllocError { layout: Layout },
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryReserveError::CapacityOverflow => f.write_str("capacity overflow"),
+            TryReserveError::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
         }
-        ptr
     }
 }
 
-pub fn drop_contents<T>(ptr: *mut T, size: usize) {
-    for i in 0..size {
+/// Source of memory blocks for `MyVector`.
+///
+/// # Safety
+///
+/// A block returned by `allocate` must be valid for reads and writes of
+/// `layout.size()` bytes, aligned to `layout.align()`, and must stay valid
+/// until it is passed to `deallocate` with the same layout. Blocks must not
+/// overlap while they are live.
+pub unsafe trait Allocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError>;
+
+    /// # Safety
+    ///
+    /// `ptr` must have come from `allocate` on this allocator with `layout`,
+    /// and must not be used afterwards.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// Resizes a block, growing or shrinking it. The first
+    /// `min(old_layout.size(), new_layout.size())` bytes are preserved. The
+    /// default allocates a new block, copies and frees the old one;
+    /// allocators that can resize in place should override it.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have come from `allocate` on this allocator with
+    /// `old_layout`, and `new_layout` must have the same alignment. On success
+    /// the old pointer must not be used again; on failure the old block is
+    /// left untouched and still owned by the caller.
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        let new_ptr = self.allocate(new_layout)?;
         unsafe {
-            ptr::drop_in_place(ptr.add(i));
+            let count = old_layout.size().min(new_layout.size());
+            ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), count);
+            self.deallocate(ptr, old_layout);
+        }
+        Ok(new_ptr)
+    }
+}
+
+// The process-wide allocator behind `alloc::alloc`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        // `alloc` must not be called with a zero size.
+        if layout.size() == 0 {
+            return Ok(dangling(layout));
         }
+
+        NonNull::new(unsafe { alloc(layout) }).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0 {
+            unsafe { dealloc(ptr.as_ptr(), layout) }
+        }
+    }
+
+    // `realloc` can often extend or shrink the block in place, and otherwise
+    // moves it with a single copy inside the system allocator.
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        if old_layout.size() == 0 {
+            return self.allocate(new_layout);
+        }
+
+        if new_layout.size() == 0 {
+            unsafe { self.deallocate(ptr, old_layout) };
+            return Ok(dangling(new_layout));
+        }
+
+        NonNull::new(unsafe { realloc(ptr.as_ptr(), old_layout, new_layout.size()) })
+            .ok_or(AllocError)
+    }
+}
+
+// Lets a vector borrow an allocator that outlives it, e.g. an arena.
+unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { (**self).deallocate(ptr, layout) }
+    }
+
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        unsafe { (**self).reallocate(ptr, old_layout, new_layout) }
+    }
+}
+
+fn dangling(layout: Layout) -> NonNull<u8> {
+    NonNull::new(ptr::without_provenance_mut(layout.align())).unwrap()
+}
+
+pub fn try_allocate_block<T, A: Allocator>(
+    allocator: &A,
+    size: usize,
+) -> Result<*mut T, TryReserveError> {
+    let layout = Layout::array::<T>(size).map_err(|_| TryReserveError::CapacityOverflow)?;
+    match allocator.allocate(layout) {
+        Ok(ptr) => Ok(ptr.as_ptr() as *mut T),
+        Err(AllocError) => Err(TryReserveError::AllocError { layout }),
+    }
+}
+
+pub fn allocate_block<T, A: Allocator>(allocator: &A, size: usize) -> *mut T {
+    handle_reserve(try_allocate_block(allocator, size))
+}
+
+/// Resizes a block from `allocate_block` to hold `new_size` values, keeping
+/// the first `min(old_size, new_size)` of them. On error the old block is
+/// untouched.
+///
+/// # Safety
+///
+/// `ptr` must have come from `allocate_block` or `try_reallocate_block` on
+/// `allocator` with `old_size`, and both sizes must be nonzero. On success
+/// `ptr` must not be used again.
+pub unsafe fn try_reallocate_block<T, A: Allocator>(
+    allocator: &A,
+    ptr: *mut T,
+    old_size: usize,
+    new_size: usize,
+) -> Result<*mut T, TryReserveError> {
+    let old_layout = Layout::array::<T>(old_size).unwrap();
+    let new_layout = Layout::array::<T>(new_size).map_err(|_| TryReserveError::CapacityOverflow)?;
+
+    let result = unsafe {
+        allocator.reallocate(
+            NonNull::new_unchecked(ptr as *mut u8),
+            old_layout,
+            new_layout,
+        )
+    };
+
+    match result {
+        Ok(ptr) => Ok(ptr.as_ptr() as *mut T),
+        Err(AllocError) => Err(TryReserveError::AllocError { layout: new_layout }),
+    }
+}
+
+// Turns a fallible result into the infallible API's behaviour: a panic on
+// overflow, the global OOM handler on allocator failure.
+pub fn handle_reserve<T>(result: Result<T, TryReserveError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+        Err(TryReserveError::AllocError { layout }) => handle_alloc_error(layout),
+    }
+}
+
+/// Drops `size` values in place. Dropping them as one slice keeps going
+/// after a destructor panics, so the rest are still dropped before the panic
+/// continues; a second panic aborts, as for any slice.
+///
+/// # Safety
+///
+/// `ptr` must point to `size` initialized values of `T` that are not used
+/// again afterwards.
+pub unsafe fn drop_contents<T>(ptr: *mut T, size: usize) {
+    unsafe {
+        ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr, size));
     }
 }
 
-pub fn deallocate_block<T>(ptr: *mut T, size: usize) {
+/// # Safety
+///
+/// `ptr` must have come from `allocate_block` on `allocator` with the same
+/// `size`, and must not be used afterwards.
+pub unsafe fn deallocate_block<T, A: Allocator>(allocator: &A, ptr: *mut T, size: usize) {
     let layout = Layout::array::<T>(size).unwrap();
     unsafe {
-        dealloc(ptr as *mut u8, layout);
+        allocator.deallocate(NonNull::new_unchecked(ptr as *mut u8), layout);
     }
 }
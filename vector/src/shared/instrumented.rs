@@ -0,0 +1,173 @@
+use core::{
+    alloc::Layout,
+    fmt,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use super::allocator::{AllocError, Allocator, Global};
+
+// Allocator wrapper that counts every request it forwards to `inner`, for
+// tests and the demo: `MyVector::new_in(&instrumented)` then `stats()`.
+// Counters are relaxed atomics, so one instance can be shared across threads
+// or live in a `static`.
+#[derive(Debug, Default)]
+pub struct Instrumented<A: Allocator = Global> {
+    inner: A,
+    allocations: AtomicUsize,
+    reallocations: AtomicUsize,
+    frees: AtomicUsize,
+    failures: AtomicUsize,
+    bytes_allocated: AtomicUsize,
+    bytes_freed: AtomicUsize,
+    // Not zeroed by `reset`, so blocks that outlive a reset are still counted.
+    live_bytes: AtomicUsize,
+    peak_bytes: AtomicUsize,
+}
+
+// Snapshot of an `Instrumented` allocator. A reallocation counts its new size
+// as allocated and its old size as freed. Event and byte counts cover the time
+// since the last `reset`; live bytes cover every block still held.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub reallocations: usize,
+    pub frees: usize,
+    pub failures: usize,
+    pub bytes_allocated: usize,
+    pub bytes_freed: usize,
+    pub peak_bytes: usize,
+    live_bytes: usize,
+}
+
+impl AllocStats {
+    pub fn live_bytes(&self) -> usize {
+        self.live_bytes
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "allocs={} reallocs={} frees={} failures={} allocated={}B freed={}B live={}B peak={}B",
+            self.allocations,
+            self.reallocations,
+            self.frees,
+            self.failures,
+            self.bytes_allocated,
+            self.bytes_freed,
+            self.live_bytes(),
+            self.peak_bytes,
+        )
+    }
+}
+
+impl Instrumented {
+    pub const fn new() -> Self {
+        Self::wrap(Global)
+    }
+}
+
+impl<A: Allocator> Instrumented<A> {
+    pub const fn wrap(inner: A) -> Self {
+        Self {
+            inner,
+            allocations: AtomicUsize::new(0),
+            reallocations: AtomicUsize::new(0),
+            frees: AtomicUsize::new(0),
+            failures: AtomicUsize::new(0),
+            bytes_allocated: AtomicUsize::new(0),
+            bytes_freed: AtomicUsize::new(0),
+            live_bytes: AtomicUsize::new(0),
+            peak_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn stats(&self) -> AllocStats {
+        AllocStats {
+            allocations: self.allocations.load(Ordering::Relaxed),
+            reallocations: self.reallocations.load(Ordering::Relaxed),
+            frees: self.frees.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            bytes_allocated: self.bytes_allocated.load(Ordering::Relaxed),
+            bytes_freed: self.bytes_freed.load(Ordering::Relaxed),
+            peak_bytes: self.peak_bytes.load(Ordering::Relaxed),
+            live_bytes: self.live_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    // Zeroes the event and byte counters and restarts the peak from the bytes
+    // live now. Blocks that are still live keep counting as live, and freeing
+    // one later shows up as a free without a matching allocation.
+    pub fn reset(&self) {
+        for counter in [
+            &self.allocations,
+            &self.reallocations,
+            &self.frees,
+            &self.failures,
+            &self.bytes_allocated,
+            &self.bytes_freed,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+        self.peak_bytes
+            .store(self.live_bytes.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
+    fn record(&self, allocated: usize, freed: usize) {
+        self.bytes_allocated.fetch_add(allocated, Ordering::Relaxed);
+        self.bytes_freed.fetch_add(freed, Ordering::Relaxed);
+
+        let live = if allocated >= freed {
+            let grown = allocated - freed;
+            self.live_bytes.fetch_add(grown, Ordering::Relaxed) + grown
+        } else {
+            let shrunk = freed - allocated;
+            self.live_bytes.fetch_sub(shrunk, Ordering::Relaxed) - shrunk
+        };
+        self.peak_bytes.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_result(
+        &self,
+        result: Result<NonNull<u8>, AllocError>,
+    ) -> Result<NonNull<u8>, AllocError> {
+        if result.is_err() {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for Instrumented<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        let ptr = self.record_result(self.inner.allocate(layout))?;
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.record(layout.size(), 0);
+        Ok(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.inner.deallocate(ptr, layout) };
+        self.frees.fetch_add(1, Ordering::Relaxed);
+        self.record(0, layout.size());
+    }
+
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<u8>, AllocError> {
+        let result = unsafe { self.inner.reallocate(ptr, old_layout, new_layout) };
+        let ptr = self.record_result(result)?;
+        self.reallocations.fetch_add(1, Ordering::Relaxed);
+        self.record(new_layout.size(), old_layout.size());
+        Ok(ptr)
+    }
+}